@@ -1,4 +1,7 @@
 // src-tauri/src/file_watcher.rs
+use crate::unity::dependency::{invalidate_dependency_graph, DependencyState};
+use crate::unity::guid_index::{apply_fs_event, GuidIndexState};
+use crate::unity::localization::{invalidate_localization, LocalizationState};
 use notify::{Event, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use std::boxed::Box;
@@ -109,8 +112,14 @@ pub async fn start_watching(
     path: String,
     app_handle: AppHandle,
     state: State<'_, WatcherState>,
+    guid_index: State<'_, GuidIndexState>,
+    localization: State<'_, LocalizationState>,
+    dependency_graph: State<'_, DependencyState>,
 ) -> Result<(), String> {
     let watcher_state = state.inner();
+    let guid_index_state = guid_index.inner().clone();
+    let localization_state = localization.inner().clone();
+    let dependency_state = dependency_graph.inner().clone();
     stop_watching_internal(&state).await?;
 
     let (tx, rx) = mpsc::channel::<Result<Event, notify::Error>>();
@@ -134,6 +143,12 @@ pub async fn start_watching(
         for res in rx {
             match res {
                 Ok(event) => {
+                    // Cập nhật GUID index khi file .meta thay đổi
+                    apply_fs_event(&guid_index_state, &event);
+                    // Bảng ngôn ngữ và đồ thị dependency cache theo project, build lại ở lần đọc sau
+                    invalidate_localization(&localization_state, &event);
+                    invalidate_dependency_graph(&dependency_state, &event);
+
                     let paths: Vec<String> = event
                         .paths
                         .iter()
@@ -5,7 +5,23 @@ mod set_app_title;
 mod unity;
 mod utils;
 
-use crate::unity::prefab::prefab_hierarchy;
+use crate::unity::animation::animation_clip;
+use crate::unity::animator::animator_controller;
+use crate::unity::curve::sample_animation_clip;
+use crate::unity::data_table::export_data_tables;
+use crate::unity::dependency::{
+    asset_dependencies, asset_references, create_dependency_state, unused_assets,
+};
+use crate::unity::guid_index::{create_guid_index_state, rebuild_guid_index, resolve_guid};
+use crate::unity::localization::{create_localization_state, localization_table, localize_keys};
+use crate::unity::mono_script::mono_script_info;
+use crate::unity::package::export_asset_package;
+use crate::unity::prefab::{prefab_hierarchy, scene_hierarchy};
+use crate::unity::prefab_diff::diff_prefabs;
+use crate::unity::render::{render_prefab_animation, render_prefab_image};
+use crate::unity::snapshot::{compare_snapshots, snapshot_project};
+use crate::unity::texture::unpack_sprite_sheet;
+use crate::unity::yaml_writer::edit_unity_asset;
 use c2u::{
     c2u,
 };
@@ -23,6 +39,9 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
         .manage(create_watcher_state())
+        .manage(create_guid_index_state())
+        .manage(create_localization_state())
+        .manage(create_dependency_state())
         .invoke_handler(tauri::generate_handler![
             get_app_versions,
             download_app,
@@ -52,6 +71,27 @@ pub fn run() {
             get_recent_logs,
             clear_old_logs,
             prefab_hierarchy,
+            scene_hierarchy,
+            rebuild_guid_index,
+            resolve_guid,
+            mono_script_info,
+            animator_controller,
+            animation_clip,
+            sample_animation_clip,
+            render_prefab_animation,
+            render_prefab_image,
+            edit_unity_asset,
+            export_data_tables,
+            localization_table,
+            localize_keys,
+            snapshot_project,
+            compare_snapshots,
+            diff_prefabs,
+            asset_dependencies,
+            asset_references,
+            unused_assets,
+            export_asset_package,
+            unpack_sprite_sheet,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,401 @@
+use crate::unity::document::parse_unity_documents;
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use crate::unity::prefab::{
+    sprite_to_base64_with_rect, AnimationClip, AnimationCurve, NullableVector3, NullableVector4,
+    SpriteRectInfo, Vector2,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+const CLASS_ANIMATION_CLIP: i64 = 74;
+// Class ID của Transform, dùng cho các track position/rotation/scale
+const CLASS_TRANSFORM: i64 = 4;
+// Weight mặc định Unity dùng khi keyframe không có inWeight/outWeight
+const DEFAULT_WEIGHT: f64 = 1.0 / 3.0;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackKind {
+    Float,
+    Vector3,
+    Quaternion,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackKeyframe {
+    pub time: f64,
+    /// One value per component (1 for Float, 3 for Vector3, 4 for Quaternion)
+    pub value: Vec<f64>,
+    /// `None` means Unity stored `Infinity`, i.e. a stepped (constant) tangent
+    pub in_slope: Vec<Option<f64>>,
+    pub out_slope: Vec<Option<f64>>,
+    pub in_weight: Vec<f64>,
+    pub out_weight: Vec<f64>,
+    pub tangent_mode: i64,
+    pub weighted_mode: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationTrack {
+    /// Transform path relative to the animated root ("" is the root itself)
+    pub path: String,
+    /// m_LocalPosition, m_LocalScale, localEulerAnglesRaw, m_LocalRotation hoặc thuộc tính float
+    pub attribute: String,
+    pub class_id: i64,
+    pub kind: TrackKind,
+    pub pre_infinity: i64,
+    pub post_infinity: i64,
+    pub keyframes: Vec<TrackKeyframe>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteKeyframe {
+    pub time: f64,
+    pub guid: Option<String>,
+    pub file_id: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteTrack {
+    pub path: String,
+    pub attribute: String,
+    pub class_id: i64,
+    pub keyframes: Vec<SpriteKeyframe>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipSprite {
+    pub guid: String,
+    pub file_id: i64,
+    pub path: Option<String>,
+    pub sprite_base64: Option<String>,
+    pub sprite_rect: Option<SpriteRectInfo>,
+    pub offset: Option<Vector2>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationEventInfo {
+    pub time: f64,
+    pub function_name: String,
+    pub string_parameter: String,
+    pub float_parameter: f64,
+    pub int_parameter: i64,
+    pub object_reference_guid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationClipData {
+    pub name: String,
+    pub path: String,
+    pub sample_rate: f64,
+    pub start_time: f64,
+    pub stop_time: f64,
+    pub length: f64,
+    pub loop_time: bool,
+    pub wrap_mode: i64,
+    /// Sorted by (path, attribute)
+    pub tracks: Vec<AnimationTrack>,
+    pub sprite_tracks: Vec<SpriteTrack>,
+    /// Every sprite referenced by `sprite_tracks`, without duplicates
+    pub sprites: Vec<ClipSprite>,
+    pub events: Vec<AnimationEventInfo>,
+}
+
+impl AnimationClipData {
+    pub fn track(&self, path: &str, attribute: &str) -> Option<&AnimationTrack> {
+        self.tracks
+            .iter()
+            .find(|t| t.path == path && t.attribute == attribute)
+    }
+}
+
+fn vector3_components(v: &Option<NullableVector3>) -> Vec<Option<f64>> {
+    match v {
+        Some(v) => [v.x, v.y, v.z]
+            .iter()
+            .map(|c| c.map(|c| c as f64))
+            .collect(),
+        None => vec![None; 3],
+    }
+}
+
+fn vector4_components(v: &Option<NullableVector4>) -> Vec<Option<f64>> {
+    match v {
+        Some(v) => [v.x, v.y, v.z, v.w]
+            .iter()
+            .map(|c| c.map(|c| c as f64))
+            .collect(),
+        None => vec![None; 4],
+    }
+}
+
+fn weights(components: Vec<Option<f64>>) -> Vec<f64> {
+    components
+        .into_iter()
+        .map(|w| w.unwrap_or(DEFAULT_WEIGHT))
+        .collect()
+}
+
+fn float_track(
+    curve: &AnimationCurve,
+    path: &str,
+    attribute: &str,
+    class_id: i64,
+) -> AnimationTrack {
+    AnimationTrack {
+        path: path.to_string(),
+        attribute: attribute.to_string(),
+        class_id,
+        kind: TrackKind::Float,
+        pre_infinity: curve.m_pre_infinity,
+        post_infinity: curve.m_post_infinity,
+        keyframes: curve
+            .m_curve
+            .iter()
+            .map(|k| TrackKeyframe {
+                time: k.time.unwrap_or(0.0),
+                value: vec![k.value.unwrap_or(0.0)],
+                in_slope: vec![k.in_slope],
+                out_slope: vec![k.out_slope],
+                in_weight: vec![k.in_weight.unwrap_or(DEFAULT_WEIGHT)],
+                out_weight: vec![k.out_weight.unwrap_or(DEFAULT_WEIGHT)],
+                tangent_mode: k.tangent_mode,
+                weighted_mode: k.weighted_mode,
+            })
+            .collect(),
+    }
+}
+
+/// Flatten every curve of a clip into tracks keyed by transform path and attribute
+pub fn clip_tracks(clip: &AnimationClip) -> Vec<AnimationTrack> {
+    let mut tracks = Vec::new();
+
+    let vector_curves = clip
+        .m_position_curves
+        .iter()
+        .map(|c| (&c.curve, &c.path, "m_LocalPosition"))
+        .chain(
+            clip.m_euler_curves
+                .iter()
+                .map(|c| (&c.curve, &c.path, "localEulerAnglesRaw")),
+        )
+        .chain(
+            clip.m_scale_curves
+                .iter()
+                .map(|c| (&c.curve, &c.path, "m_LocalScale")),
+        );
+    for (curve, path, attribute) in vector_curves {
+        tracks.push(AnimationTrack {
+            path: path.clone(),
+            attribute: attribute.to_string(),
+            class_id: CLASS_TRANSFORM,
+            kind: TrackKind::Vector3,
+            pre_infinity: curve.m_pre_infinity,
+            post_infinity: curve.m_post_infinity,
+            keyframes: curve
+                .m_curve
+                .iter()
+                .map(|k| TrackKeyframe {
+                    time: k.time.unwrap_or(0.0),
+                    value: vec![k.value.x as f64, k.value.y as f64, k.value.z as f64],
+                    in_slope: vector3_components(&k.in_slope),
+                    out_slope: vector3_components(&k.out_slope),
+                    in_weight: weights(vector3_components(&k.in_weight)),
+                    out_weight: weights(vector3_components(&k.out_weight)),
+                    tangent_mode: k.tangent_mode,
+                    weighted_mode: k.weighted_mode,
+                })
+                .collect(),
+        });
+    }
+
+    for rotation in &clip.m_rotation_curves {
+        let curve = &rotation.curve;
+        tracks.push(AnimationTrack {
+            path: rotation.path.clone(),
+            attribute: "m_LocalRotation".to_string(),
+            class_id: CLASS_TRANSFORM,
+            kind: TrackKind::Quaternion,
+            pre_infinity: curve.m_pre_infinity,
+            post_infinity: curve.m_post_infinity,
+            keyframes: curve
+                .m_curve
+                .iter()
+                .map(|k| TrackKeyframe {
+                    time: k.time.unwrap_or(0.0),
+                    value: vec![
+                        k.value.x as f64,
+                        k.value.y as f64,
+                        k.value.z as f64,
+                        k.value.w as f64,
+                    ],
+                    in_slope: vector4_components(&k.in_slope),
+                    out_slope: vector4_components(&k.out_slope),
+                    in_weight: weights(vector4_components(&k.in_weight)),
+                    out_weight: weights(vector4_components(&k.out_weight)),
+                    tangent_mode: k.tangent_mode,
+                    weighted_mode: k.weighted_mode,
+                })
+                .collect(),
+        });
+    }
+
+    for float_curve in &clip.m_float_curves {
+        tracks.push(float_track(
+            &float_curve.curve,
+            &float_curve.path,
+            &float_curve.attribute,
+            float_curve.class_id,
+        ));
+    }
+
+    tracks.sort_by(|a, b| (&a.path, &a.attribute).cmp(&(&b.path, &b.attribute)));
+    tracks
+}
+
+/// Read the AnimationClip document of a `.anim` file
+pub fn read_animation_clip(clip_path: &Path) -> Result<AnimationClip, String> {
+    let content = fs::read_to_string(clip_path)
+        .map_err(|e| format!("Failed to read animation clip: {}", e))?;
+    let document = parse_unity_documents(&content)
+        .into_iter()
+        .find(|d| d.class_id == CLASS_ANIMATION_CLIP)
+        .ok_or_else(|| format!("No AnimationClip found in: {}", clip_path.display()))?;
+    document
+        .view()
+        .map_err(|e| format!("Failed to parse AnimationClip: {}", e))
+}
+
+/// Convert a raw clip into tracks, sprite keyframes and events the frontend can play
+pub fn normalize_clip(
+    clip: &AnimationClip,
+    clip_path: &Path,
+    index: &GuidIndex,
+    with_sprites: bool,
+) -> AnimationClipData {
+    let tracks = clip_tracks(clip);
+
+    let sprite_tracks: Vec<SpriteTrack> = clip
+        .m_pptr_curves
+        .iter()
+        .map(|c| SpriteTrack {
+            path: c.path.clone(),
+            attribute: c.attribute.clone(),
+            class_id: c.class_id,
+            keyframes: c
+                .curve
+                .iter()
+                .map(|k| SpriteKeyframe {
+                    time: k.time.unwrap_or(0.0),
+                    guid: k.value.guid.clone(),
+                    file_id: k.value.file_id,
+                })
+                .collect(),
+        })
+        .collect();
+
+    // Mỗi sprite chỉ encode một lần dù xuất hiện ở nhiều keyframe
+    let mut sprites: Vec<ClipSprite> = Vec::new();
+    for keyframe in sprite_tracks.iter().flat_map(|t| &t.keyframes) {
+        let Some(guid) = &keyframe.guid else {
+            continue;
+        };
+        if sprites
+            .iter()
+            .any(|s| s.guid == *guid && s.file_id == keyframe.file_id)
+        {
+            continue;
+        }
+        let info = if with_sprites {
+            sprite_to_base64_with_rect(guid, keyframe.file_id, index).ok()
+        } else {
+            None
+        };
+        sprites.push(ClipSprite {
+            guid: guid.clone(),
+            file_id: keyframe.file_id,
+            path: index.resolve_str(guid),
+            sprite_base64: info.as_ref().and_then(|i| i.base64.clone()),
+            sprite_rect: info.as_ref().map(|i| i.rect.clone()),
+            offset: info.map(|i| i.offset),
+        });
+    }
+
+    let events = clip
+        .m_events
+        .iter()
+        .map(|e| AnimationEventInfo {
+            time: e.time,
+            function_name: e.function_name.clone(),
+            string_parameter: e.data.clone().unwrap_or_default(),
+            float_parameter: e.float_parameter,
+            int_parameter: e.int_parameter,
+            object_reference_guid: e.object_reference_parameter.guid.clone(),
+        })
+        .collect();
+
+    // Độ dài clip: ưu tiên m_StopTime, nếu không có thì lấy keyframe cuối cùng
+    let last_key_time = tracks
+        .iter()
+        .flat_map(|t| t.keyframes.iter().map(|k| k.time))
+        .chain(
+            sprite_tracks
+                .iter()
+                .flat_map(|t| t.keyframes.iter().map(|k| k.time)),
+        )
+        .fold(0.0_f64, f64::max);
+    let (start_time, stop_time, loop_time) = match &clip.m_animation_clip_settings {
+        Some(settings) if settings.m_stop_time > settings.m_start_time => (
+            settings.m_start_time,
+            settings.m_stop_time,
+            settings.m_loop_time != 0,
+        ),
+        Some(settings) => (0.0, last_key_time, settings.m_loop_time != 0),
+        None => (0.0, last_key_time, false),
+    };
+
+    AnimationClipData {
+        name: clip.m_name.clone(),
+        path: clip_path.to_string_lossy().to_string(),
+        sample_rate: clip.m_sample_rate.unwrap_or(60.0),
+        start_time,
+        stop_time,
+        length: stop_time - start_time,
+        loop_time,
+        wrap_mode: clip.m_wrap_mode,
+        tracks,
+        sprite_tracks,
+        sprites,
+        events,
+    }
+}
+
+pub fn load_animation_clip(
+    clip_path: &Path,
+    index: &GuidIndex,
+    with_sprites: bool,
+) -> Result<AnimationClipData, String> {
+    let clip = read_animation_clip(clip_path)?;
+    Ok(normalize_clip(&clip, clip_path, index, with_sprites))
+}
+
+#[tauri::command]
+pub async fn animation_clip(
+    project_path: String,
+    clip_path: Option<String>,
+    clip_guid: Option<String>,
+    include_sprites: Option<bool>,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<AnimationClipData, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let path = index.resolve_path_or_guid(clip_path.as_deref(), clip_guid.as_deref())?;
+    load_animation_clip(&path, &index, include_sprites.unwrap_or(true))
+}
@@ -0,0 +1,687 @@
+use crate::unity::document::{parse_unity_documents, UnityDocument};
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use crate::unity::prefab::FileReference;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+// Class IDs của các document trong file .controller
+const CLASS_ANIMATOR_CONTROLLER: i64 = 91;
+const CLASS_BLEND_TREE: i64 = 206;
+const CLASS_ANIMATOR_OVERRIDE_CONTROLLER: i64 = 221;
+const CLASS_ANIMATOR_STATE_TRANSITION: i64 = 1101;
+const CLASS_ANIMATOR_STATE: i64 = 1102;
+const CLASS_ANIMATOR_STATE_MACHINE: i64 = 1107;
+const CLASS_ANIMATOR_TRANSITION: i64 = 1109;
+
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatorControllerDoc {
+    #[serde(rename = "m_Name", default)]
+    m_name: Option<String>,
+    #[serde(rename = "m_AnimatorParameters", default)]
+    m_animator_parameters: Vec<AnimatorParameter>,
+    #[serde(rename = "m_AnimatorLayers", default)]
+    m_animator_layers: Vec<AnimatorLayer>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatorParameter {
+    #[serde(rename = "m_Name", default)]
+    m_name: Option<String>,
+    #[serde(rename = "m_Type", default)]
+    m_type: i64,
+    #[serde(rename = "m_DefaultFloat", default)]
+    m_default_float: f64,
+    #[serde(rename = "m_DefaultInt", default)]
+    m_default_int: i64,
+    #[serde(rename = "m_DefaultBool", default)]
+    m_default_bool: i64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatorLayer {
+    #[serde(rename = "m_Name", default)]
+    m_name: Option<String>,
+    #[serde(rename = "m_StateMachine")]
+    m_state_machine: FileReference,
+    #[serde(rename = "m_BlendingMode", default)]
+    m_blending_mode: i64,
+    #[serde(rename = "m_DefaultWeight", default)]
+    m_default_weight: f64,
+    #[serde(rename = "m_SyncedLayerIndex", default)]
+    m_synced_layer_index: i64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ChildState {
+    #[serde(rename = "m_State")]
+    m_state: FileReference,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ChildStateMachine {
+    #[serde(rename = "m_StateMachine")]
+    m_state_machine: FileReference,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatorStateMachineDoc {
+    #[serde(rename = "m_Name", default)]
+    m_name: Option<String>,
+    #[serde(rename = "m_ChildStates", default)]
+    m_child_states: Vec<ChildState>,
+    #[serde(rename = "m_ChildStateMachines", default)]
+    m_child_state_machines: Vec<ChildStateMachine>,
+    #[serde(rename = "m_AnyStateTransitions", default)]
+    m_any_state_transitions: Vec<FileReference>,
+    #[serde(rename = "m_EntryTransitions", default)]
+    m_entry_transitions: Vec<FileReference>,
+    #[serde(rename = "m_DefaultState", default)]
+    m_default_state: Option<FileReference>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatorStateDoc {
+    #[serde(rename = "m_Name", default)]
+    m_name: Option<String>,
+    #[serde(rename = "m_Speed", default)]
+    m_speed: Option<f64>,
+    #[serde(rename = "m_CycleOffset", default)]
+    m_cycle_offset: f64,
+    #[serde(rename = "m_Transitions", default)]
+    m_transitions: Vec<FileReference>,
+    #[serde(rename = "m_Motion", default)]
+    m_motion: Option<FileReference>,
+    #[serde(rename = "m_Tag", default)]
+    m_tag: Option<String>,
+    #[serde(rename = "m_WriteDefaultValues", default)]
+    m_write_default_values: i64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatorCondition {
+    #[serde(rename = "m_ConditionMode", default)]
+    m_condition_mode: i64,
+    #[serde(rename = "m_ConditionEvent", default)]
+    m_condition_event: Option<String>,
+    #[serde(rename = "m_EventTreshold", default)]
+    m_event_treshold: f64,
+}
+
+// Dùng chung cho AnimatorStateTransition (1101) và AnimatorTransition (1109)
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatorTransitionDoc {
+    #[serde(rename = "m_Name", default)]
+    m_name: Option<String>,
+    #[serde(rename = "m_Conditions", default)]
+    m_conditions: Vec<AnimatorCondition>,
+    #[serde(rename = "m_DstState", default)]
+    m_dst_state: Option<FileReference>,
+    #[serde(rename = "m_DstStateMachine", default)]
+    m_dst_state_machine: Option<FileReference>,
+    #[serde(rename = "m_Mute", default)]
+    m_mute: i64,
+    #[serde(rename = "m_Solo", default)]
+    m_solo: i64,
+    #[serde(rename = "m_IsExit", default)]
+    m_is_exit: i64,
+    #[serde(rename = "m_TransitionDuration", default)]
+    m_transition_duration: f64,
+    #[serde(rename = "m_TransitionOffset", default)]
+    m_transition_offset: f64,
+    #[serde(rename = "m_ExitTime", default)]
+    m_exit_time: f64,
+    #[serde(rename = "m_HasExitTime", default)]
+    m_has_exit_time: i64,
+    #[serde(rename = "m_HasFixedDuration", default)]
+    m_has_fixed_duration: i64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct BlendTreeChild {
+    #[serde(rename = "m_Motion")]
+    m_motion: FileReference,
+    #[serde(rename = "m_Threshold", default)]
+    m_threshold: f64,
+    #[serde(rename = "m_TimeScale", default)]
+    m_time_scale: Option<f64>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct BlendTreeDoc {
+    #[serde(rename = "m_Name", default)]
+    m_name: Option<String>,
+    #[serde(rename = "m_Childs", default)]
+    m_childs: Vec<BlendTreeChild>,
+    #[serde(rename = "m_BlendParameter", default)]
+    m_blend_parameter: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct OverrideClip {
+    #[serde(rename = "m_OriginalClip")]
+    m_original_clip: FileReference,
+    #[serde(rename = "m_OverrideClip")]
+    m_override_clip: FileReference,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct AnimatorOverrideControllerDoc {
+    #[serde(rename = "m_Name", default)]
+    m_name: Option<String>,
+    #[serde(rename = "m_Controller")]
+    m_controller: FileReference,
+    #[serde(rename = "m_Clips", default)]
+    m_clips: Vec<OverrideClip>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatorParameterInfo {
+    pub name: String,
+    /// Float, Int, Bool hoặc Trigger
+    pub parameter_type: String,
+    pub default_value: serde_json::Value,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatorConditionInfo {
+    /// If, IfNot, Greater, Less, Equals hoặc NotEqual
+    pub mode: String,
+    pub parameter: String,
+    pub threshold: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatorTransitionInfo {
+    pub file_id: i64,
+    pub name: String,
+    pub destination_state_id: Option<i64>,
+    pub destination_name: Option<String>,
+    pub is_exit: bool,
+    pub muted: bool,
+    pub solo: bool,
+    pub has_exit_time: bool,
+    pub exit_time: f64,
+    pub duration: f64,
+    pub offset: f64,
+    pub has_fixed_duration: bool,
+    pub conditions: Vec<AnimatorConditionInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatorMotionInfo {
+    pub name: String,
+    pub file_id: i64,
+    pub guid: Option<String>,
+    /// Đường dẫn file .anim (hoặc model) chứa clip
+    pub clip_path: Option<String>,
+    pub is_blend_tree: bool,
+    pub blend_parameter: Option<String>,
+    pub threshold: Option<f64>,
+    pub children: Vec<AnimatorMotionInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatorStateInfo {
+    pub file_id: i64,
+    pub name: String,
+    pub tag: String,
+    pub speed: f64,
+    pub cycle_offset: f64,
+    pub write_default_values: bool,
+    pub is_default: bool,
+    pub motion: Option<AnimatorMotionInfo>,
+    pub transitions: Vec<AnimatorTransitionInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatorStateMachineInfo {
+    pub file_id: i64,
+    pub name: String,
+    pub default_state_id: Option<i64>,
+    pub states: Vec<AnimatorStateInfo>,
+    pub any_state_transitions: Vec<AnimatorTransitionInfo>,
+    pub entry_transitions: Vec<AnimatorTransitionInfo>,
+    pub state_machines: Vec<AnimatorStateMachineInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatorLayerInfo {
+    pub name: String,
+    pub blending_mode: i64,
+    pub default_weight: f64,
+    pub synced_layer_index: i64,
+    pub state_machine: Option<AnimatorStateMachineInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationClipRef {
+    pub name: String,
+    pub guid: Option<String>,
+    pub file_id: i64,
+    pub path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimatorControllerGraph {
+    pub name: String,
+    pub path: String,
+    /// Controller gốc khi file là AnimatorOverrideController
+    pub base_controller_path: Option<String>,
+    pub parameters: Vec<AnimatorParameterInfo>,
+    pub layers: Vec<AnimatorLayerInfo>,
+    /// Tất cả clip được dùng trong graph, không trùng lặp
+    pub clips: Vec<AnimationClipRef>,
+}
+
+fn parameter_type_name(m_type: i64) -> &'static str {
+    match m_type {
+        1 => "Float",
+        3 => "Int",
+        4 => "Bool",
+        9 => "Trigger",
+        _ => "Unknown",
+    }
+}
+
+fn condition_mode_name(mode: i64) -> &'static str {
+    match mode {
+        1 => "If",
+        2 => "IfNot",
+        3 => "Greater",
+        4 => "Less",
+        6 => "Equals",
+        7 => "NotEqual",
+        _ => "Unknown",
+    }
+}
+
+fn deserialize_inner<T: serde::de::DeserializeOwned>(document: &UnityDocument) -> Option<T> {
+    document.view().ok()
+}
+
+fn reference_id(reference: &Option<FileReference>) -> Option<i64> {
+    reference.as_ref().map(|r| r.file_id).filter(|id| *id != 0)
+}
+
+fn clip_key(reference: &FileReference) -> String {
+    format!(
+        "{}:{}",
+        reference.guid.as_deref().unwrap_or_default(),
+        reference.file_id
+    )
+}
+
+struct ControllerGraphBuilder<'a> {
+    index: &'a GuidIndex,
+    states: HashMap<i64, AnimatorStateDoc>,
+    state_machines: HashMap<i64, AnimatorStateMachineDoc>,
+    transitions: HashMap<i64, AnimatorTransitionDoc>,
+    blend_trees: HashMap<i64, BlendTreeDoc>,
+    overrides: HashMap<String, FileReference>,
+    clips: Vec<AnimationClipRef>,
+    visited_machines: HashSet<i64>,
+}
+
+impl<'a> ControllerGraphBuilder<'a> {
+    fn new(documents: &[UnityDocument], index: &'a GuidIndex) -> Self {
+        let mut builder = ControllerGraphBuilder {
+            index,
+            states: HashMap::new(),
+            state_machines: HashMap::new(),
+            transitions: HashMap::new(),
+            blend_trees: HashMap::new(),
+            overrides: HashMap::new(),
+            clips: Vec::new(),
+            visited_machines: HashSet::new(),
+        };
+        for document in documents {
+            let id = document.file_id;
+            match document.class_id {
+                CLASS_ANIMATOR_STATE => {
+                    if let Some(state) = deserialize_inner(document) {
+                        builder.states.insert(id, state);
+                    }
+                }
+                CLASS_ANIMATOR_STATE_MACHINE => {
+                    if let Some(machine) = deserialize_inner(document) {
+                        builder.state_machines.insert(id, machine);
+                    }
+                }
+                CLASS_ANIMATOR_STATE_TRANSITION | CLASS_ANIMATOR_TRANSITION => {
+                    if let Some(transition) = deserialize_inner(document) {
+                        builder.transitions.insert(id, transition);
+                    }
+                }
+                CLASS_BLEND_TREE => {
+                    if let Some(tree) = deserialize_inner(document) {
+                        builder.blend_trees.insert(id, tree);
+                    }
+                }
+                _ => {}
+            }
+        }
+        builder
+    }
+
+    fn register_clip(&mut self, reference: &FileReference, name: &str, path: &Option<String>) {
+        let exists = self
+            .clips
+            .iter()
+            .any(|c| c.guid == reference.guid && c.file_id == reference.file_id);
+        if exists {
+            return;
+        }
+        self.clips.push(AnimationClipRef {
+            name: name.to_string(),
+            guid: reference.guid.clone(),
+            file_id: reference.file_id,
+            path: path.clone(),
+        });
+    }
+
+    fn build_motion(
+        &mut self,
+        reference: &FileReference,
+        threshold: Option<f64>,
+        depth: usize,
+    ) -> Option<AnimatorMotionInfo> {
+        if reference.file_id == 0 {
+            return None;
+        }
+
+        // Motion nằm trong file khác (.anim), áp dụng override nếu có
+        if reference.guid.is_some() {
+            let reference = self
+                .overrides
+                .get(&clip_key(reference))
+                .cloned()
+                .unwrap_or_else(|| reference.clone());
+            let guid = reference.guid.clone().unwrap_or_default();
+            let clip_path = self.index.resolve_str(&guid);
+            let name = clip_path
+                .as_ref()
+                .and_then(|p| Path::new(p).file_stem())
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| guid.clone());
+            self.register_clip(&reference, &name, &clip_path);
+            return Some(AnimatorMotionInfo {
+                name,
+                file_id: reference.file_id,
+                guid: Some(guid),
+                clip_path,
+                is_blend_tree: false,
+                blend_parameter: None,
+                threshold,
+                children: Vec::new(),
+            });
+        }
+
+        // Motion nội bộ: BlendTree trong cùng file controller
+        let tree = self.blend_trees.get(&reference.file_id)?.clone();
+        let children = if depth < 16 {
+            tree.m_childs
+                .iter()
+                .filter_map(|child| {
+                    self.build_motion(&child.m_motion, Some(child.m_threshold), depth + 1)
+                        .map(|mut motion| {
+                            if let Some(time_scale) = child.m_time_scale {
+                                if time_scale != 1.0 {
+                                    motion.name = format!("{} (x{})", motion.name, time_scale);
+                                }
+                            }
+                            motion
+                        })
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Some(AnimatorMotionInfo {
+            name: tree.m_name.unwrap_or_else(|| "Blend Tree".to_string()),
+            file_id: reference.file_id,
+            guid: None,
+            clip_path: None,
+            is_blend_tree: true,
+            blend_parameter: tree.m_blend_parameter,
+            threshold,
+            children,
+        })
+    }
+
+    fn build_transition(&self, file_id: i64) -> Option<AnimatorTransitionInfo> {
+        let transition = self.transitions.get(&file_id)?;
+        let destination_state_id = reference_id(&transition.m_dst_state);
+        let destination_name = match destination_state_id {
+            Some(id) => self.states.get(&id).and_then(|s| s.m_name.clone()),
+            None => reference_id(&transition.m_dst_state_machine)
+                .and_then(|id| self.state_machines.get(&id))
+                .and_then(|m| m.m_name.clone()),
+        };
+        Some(AnimatorTransitionInfo {
+            file_id,
+            name: transition.m_name.clone().unwrap_or_default(),
+            destination_state_id,
+            destination_name,
+            is_exit: transition.m_is_exit != 0,
+            muted: transition.m_mute != 0,
+            solo: transition.m_solo != 0,
+            has_exit_time: transition.m_has_exit_time != 0,
+            exit_time: transition.m_exit_time,
+            duration: transition.m_transition_duration,
+            offset: transition.m_transition_offset,
+            has_fixed_duration: transition.m_has_fixed_duration != 0,
+            conditions: transition
+                .m_conditions
+                .iter()
+                .map(|c| AnimatorConditionInfo {
+                    mode: condition_mode_name(c.m_condition_mode).to_string(),
+                    parameter: c.m_condition_event.clone().unwrap_or_default(),
+                    threshold: c.m_event_treshold,
+                })
+                .collect(),
+        })
+    }
+
+    fn build_state(
+        &mut self,
+        file_id: i64,
+        default_state_id: Option<i64>,
+    ) -> Option<AnimatorStateInfo> {
+        let state = self.states.get(&file_id)?.clone();
+        let motion = state
+            .m_motion
+            .as_ref()
+            .and_then(|m| self.build_motion(m, None, 0));
+        let transitions = state
+            .m_transitions
+            .iter()
+            .filter_map(|t| self.build_transition(t.file_id))
+            .collect();
+        Some(AnimatorStateInfo {
+            file_id,
+            name: state.m_name.unwrap_or_default(),
+            tag: state.m_tag.unwrap_or_default(),
+            speed: state.m_speed.unwrap_or(1.0),
+            cycle_offset: state.m_cycle_offset,
+            write_default_values: state.m_write_default_values != 0,
+            is_default: default_state_id == Some(file_id),
+            motion,
+            transitions,
+        })
+    }
+
+    fn build_state_machine(&mut self, file_id: i64) -> Option<AnimatorStateMachineInfo> {
+        // Tránh vòng lặp giữa các sub state machine
+        if !self.visited_machines.insert(file_id) {
+            return None;
+        }
+        let machine = self.state_machines.get(&file_id)?.clone();
+        let default_state_id = reference_id(&machine.m_default_state);
+
+        let states = machine
+            .m_child_states
+            .iter()
+            .filter_map(|child| self.build_state(child.m_state.file_id, default_state_id))
+            .collect();
+        let any_state_transitions = machine
+            .m_any_state_transitions
+            .iter()
+            .filter_map(|t| self.build_transition(t.file_id))
+            .collect();
+        let entry_transitions = machine
+            .m_entry_transitions
+            .iter()
+            .filter_map(|t| self.build_transition(t.file_id))
+            .collect();
+        let state_machines = machine
+            .m_child_state_machines
+            .iter()
+            .filter_map(|child| self.build_state_machine(child.m_state_machine.file_id))
+            .collect();
+
+        Some(AnimatorStateMachineInfo {
+            file_id,
+            name: machine.m_name.unwrap_or_default(),
+            default_state_id,
+            states,
+            any_state_transitions,
+            entry_transitions,
+            state_machines,
+        })
+    }
+}
+
+fn read_controller_documents(controller_path: &Path) -> Result<Vec<UnityDocument>, String> {
+    let content = fs::read_to_string(controller_path)
+        .map_err(|e| format!("Failed to read controller file: {}", e))?;
+    Ok(parse_unity_documents(&content))
+}
+
+fn build_controller_graph(
+    documents: &[UnityDocument],
+    controller_path: &Path,
+    index: &GuidIndex,
+    overrides: HashMap<String, FileReference>,
+) -> Result<AnimatorControllerGraph, String> {
+    let controller: AnimatorControllerDoc = documents
+        .iter()
+        .find(|d| d.class_id == CLASS_ANIMATOR_CONTROLLER)
+        .and_then(deserialize_inner)
+        .ok_or_else(|| {
+            format!(
+                "No AnimatorController found in: {}",
+                controller_path.display()
+            )
+        })?;
+
+    let mut builder = ControllerGraphBuilder::new(documents, index);
+    builder.overrides = overrides;
+
+    let parameters = controller
+        .m_animator_parameters
+        .iter()
+        .map(|p| AnimatorParameterInfo {
+            name: p.m_name.clone().unwrap_or_default(),
+            parameter_type: parameter_type_name(p.m_type).to_string(),
+            default_value: match p.m_type {
+                1 => serde_json::Value::from(p.m_default_float),
+                3 => serde_json::Value::from(p.m_default_int),
+                4 => serde_json::Value::from(p.m_default_bool != 0),
+                _ => serde_json::Value::Null,
+            },
+        })
+        .collect();
+
+    let layers = controller
+        .m_animator_layers
+        .iter()
+        .map(|layer| AnimatorLayerInfo {
+            name: layer.m_name.clone().unwrap_or_default(),
+            blending_mode: layer.m_blending_mode,
+            default_weight: layer.m_default_weight,
+            synced_layer_index: layer.m_synced_layer_index,
+            state_machine: builder.build_state_machine(layer.m_state_machine.file_id),
+        })
+        .collect();
+
+    Ok(AnimatorControllerGraph {
+        name: controller.m_name.unwrap_or_default(),
+        path: controller_path.to_string_lossy().to_string(),
+        base_controller_path: None,
+        parameters,
+        layers,
+        clips: builder.clips,
+    })
+}
+
+/// Parse a `.controller` (or `.overrideController`) file into its full state graph
+pub fn load_animator_controller(
+    controller_path: &Path,
+    index: &GuidIndex,
+) -> Result<AnimatorControllerGraph, String> {
+    let documents = read_controller_documents(controller_path)?;
+
+    let override_controller: Option<AnimatorOverrideControllerDoc> = documents
+        .iter()
+        .find(|d| d.class_id == CLASS_ANIMATOR_OVERRIDE_CONTROLLER)
+        .and_then(deserialize_inner);
+
+    let Some(override_controller) = override_controller else {
+        return build_controller_graph(&documents, controller_path, index, HashMap::new());
+    };
+
+    // Override controller: dựng graph của controller gốc rồi thay clip
+    let base_guid = override_controller
+        .m_controller
+        .guid
+        .clone()
+        .ok_or("Override controller has no base controller")?;
+    let base_path = index
+        .resolve(&base_guid)
+        .ok_or_else(|| format!("Base controller not found for GUID: {}", base_guid))?;
+    let overrides = override_controller
+        .m_clips
+        .iter()
+        .filter(|c| c.m_override_clip.file_id != 0)
+        .map(|c| (clip_key(&c.m_original_clip), c.m_override_clip.clone()))
+        .collect();
+
+    let base_documents = read_controller_documents(&base_path)?;
+    let mut graph = build_controller_graph(&base_documents, &base_path, index, overrides)?;
+    graph.name = override_controller.m_name.unwrap_or(graph.name);
+    graph.path = controller_path.to_string_lossy().to_string();
+    graph.base_controller_path = Some(base_path.to_string_lossy().to_string());
+    Ok(graph)
+}
+
+/// Names of every clip reachable from a controller, used to fill `HierarchyNode.animation_clips`
+pub fn controller_clip_names(controller_guid: &str, index: &GuidIndex) -> Option<Vec<String>> {
+    let controller_path = index.resolve(controller_guid)?;
+    let graph = load_animator_controller(&controller_path, index).ok()?;
+    Some(graph.clips.into_iter().map(|c| c.name).collect())
+}
+
+#[tauri::command]
+pub async fn animator_controller(
+    project_path: String,
+    controller_path: Option<String>,
+    controller_guid: Option<String>,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<AnimatorControllerGraph, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let path =
+        index.resolve_path_or_guid(controller_path.as_deref(), controller_guid.as_deref())?;
+    load_animator_controller(&path, &index)
+}
@@ -0,0 +1,301 @@
+use crate::unity::animation::{
+    load_animation_clip, AnimationClipData, AnimationTrack, SpriteKeyframe, SpriteTrack,
+    TrackKeyframe, TrackKind,
+};
+use crate::unity::guid_index::{project_index, GuidIndexState};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::State;
+
+// Giá trị m_PreInfinity/m_PostInfinity trong file YAML (khác với enum WrapMode của C#)
+const INFINITY_PING_PONG: i64 = 0;
+const INFINITY_REPEAT: i64 = 1;
+
+// tangentMode: bit 0 = broken, bit 1-4 = left tangent, bit 5-8 = right tangent
+const TANGENT_CONSTANT: i64 = 3;
+
+// weightedMode: 1 = In, 2 = Out, 3 = Both
+const WEIGHTED_IN: i64 = 1;
+const WEIGHTED_OUT: i64 = 2;
+
+const MAX_SAMPLED_FRAMES: usize = 10_000;
+
+fn left_tangent_mode(tangent_mode: i64) -> i64 {
+    (tangent_mode >> 1) & 0xF
+}
+
+fn right_tangent_mode(tangent_mode: i64) -> i64 {
+    (tangent_mode >> 5) & 0xF
+}
+
+/// Map a time outside the key range back into it according to the wrap mode
+fn wrap_time(time: f64, start: f64, end: f64, mode: i64) -> f64 {
+    let length = end - start;
+    if length <= 0.0 {
+        return start;
+    }
+    match mode {
+        INFINITY_REPEAT => start + (time - start).rem_euclid(length),
+        INFINITY_PING_PONG => {
+            let cycle = (time - start).rem_euclid(length * 2.0);
+            if cycle <= length {
+                start + cycle
+            } else {
+                end - (cycle - length)
+            }
+        }
+        // Clamp (2) và các giá trị khác
+        _ => time.clamp(start, end),
+    }
+}
+
+fn hermite(t: f64, p0: f64, m0: f64, m1: f64, p1: f64, dt: f64) -> f64 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let a = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let b = t3 - 2.0 * t2 + t;
+    let c = t3 - t2;
+    let d = -2.0 * t3 + 3.0 * t2;
+    a * p0 + b * m0 * dt + c * m1 * dt + d * p1
+}
+
+fn bezier(u: f64, p0: f64, p1: f64, p2: f64, p3: f64) -> f64 {
+    let inv = 1.0 - u;
+    inv * inv * inv * p0 + 3.0 * inv * inv * u * p1 + 3.0 * inv * u * u * p2 + u * u * u * p3
+}
+
+// Weighted tangents: cubic Bezier theo cả trục thời gian, tìm tham số u bằng chia đôi
+// start/end = (time, value, slope, weight)
+fn weighted_segment(t: f64, start: (f64, f64, f64, f64), end: (f64, f64, f64, f64)) -> f64 {
+    let (t0, v0, out_slope, out_weight) = start;
+    let (t1, v1, in_slope, in_weight) = end;
+    let dt = t1 - t0;
+    let c1_time = t0 + out_weight * dt;
+    let c2_time = t1 - in_weight * dt;
+    let c1_value = v0 + out_weight * dt * out_slope;
+    let c2_value = v1 - in_weight * dt * in_slope;
+
+    let (mut lo, mut hi) = (0.0_f64, 1.0_f64);
+    let mut u = (t - t0) / dt;
+    for _ in 0..48 {
+        let x = bezier(u, t0, c1_time, c2_time, t1);
+        if (x - t).abs() < 1e-7 {
+            break;
+        }
+        if x < t {
+            lo = u;
+        } else {
+            hi = u;
+        }
+        u = (lo + hi) * 0.5;
+    }
+    bezier(u, v0, c1_value, c2_value, v1)
+}
+
+fn evaluate_segment(k0: &TrackKeyframe, k1: &TrackKeyframe, component: usize, time: f64) -> f64 {
+    let v0 = k0.value[component];
+    let v1 = k1.value[component];
+    let dt = k1.time - k0.time;
+    if dt <= 0.0 {
+        return v1;
+    }
+
+    // Stepped: slope vô hạn hoặc tangent Constant
+    let (Some(out_slope), Some(in_slope)) = (
+        k0.out_slope.get(component).copied().flatten(),
+        k1.in_slope.get(component).copied().flatten(),
+    ) else {
+        return v0;
+    };
+    if right_tangent_mode(k0.tangent_mode) == TANGENT_CONSTANT
+        || left_tangent_mode(k1.tangent_mode) == TANGENT_CONSTANT
+        || !out_slope.is_finite()
+        || !in_slope.is_finite()
+    {
+        return v0;
+    }
+
+    let out_weighted = k0.weighted_mode & WEIGHTED_OUT != 0;
+    let in_weighted = k1.weighted_mode & WEIGHTED_IN != 0;
+    if out_weighted || in_weighted {
+        let default_weight = 1.0 / 3.0;
+        let out_weight = if out_weighted {
+            k0.out_weight[component]
+        } else {
+            default_weight
+        };
+        let in_weight = if in_weighted {
+            k1.in_weight[component]
+        } else {
+            default_weight
+        };
+        return weighted_segment(
+            time,
+            (k0.time, v0, out_slope, out_weight),
+            (k1.time, v1, in_slope, in_weight),
+        );
+    }
+
+    hermite((time - k0.time) / dt, v0, out_slope, in_slope, v1, dt)
+}
+
+/// Evaluate every component of a track at `time`, Unity style
+pub fn evaluate_track(track: &AnimationTrack, time: f64) -> Option<Vec<f64>> {
+    let keys = &track.keyframes;
+    let first = keys.first()?;
+    let last = keys.last()?;
+    let components = first.value.len();
+    if keys.len() == 1 {
+        return Some(first.value.clone());
+    }
+
+    let time = if time < first.time {
+        wrap_time(time, first.time, last.time, track.pre_infinity)
+    } else if time > last.time {
+        wrap_time(time, first.time, last.time, track.post_infinity)
+    } else {
+        time
+    };
+
+    // Keyframe cuối cùng có time <= thời điểm cần tính
+    let i = keys
+        .partition_point(|k| k.time <= time)
+        .saturating_sub(1)
+        .min(keys.len() - 2);
+    let (k0, k1) = (&keys[i], &keys[i + 1]);
+
+    let mut values: Vec<f64> = (0..components)
+        .map(|c| evaluate_segment(k0, k1, c, time))
+        .collect();
+
+    if track.kind == TrackKind::Quaternion {
+        let length = values.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if length > 0.0 {
+            values.iter_mut().for_each(|v| *v /= length);
+        }
+    }
+    Some(values)
+}
+
+/// Sprite shown by a PPtr track at `time` (PPtr curves are always stepped)
+pub fn evaluate_sprite_track(track: &SpriteTrack, time: f64) -> Option<&SpriteKeyframe> {
+    let i = track.keyframes.partition_point(|k| k.time <= time);
+    track.keyframes.get(i.saturating_sub(1))
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformPose {
+    /// Transform path relative to the animated root
+    pub path: String,
+    pub local_position: Option<[f64; 3]>,
+    pub local_euler_angles: Option<[f64; 3]>,
+    pub local_rotation: Option<[f64; 4]>,
+    pub local_scale: Option<[f64; 3]>,
+    /// Other animated float properties (m_IsActive, m_Color.a, m_FlipX...)
+    pub floats: BTreeMap<String, f64>,
+    pub sprite: Option<SpriteKeyframe>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PoseFrame {
+    pub time: f64,
+    pub transforms: Vec<TransformPose>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipSamples {
+    pub clip_name: String,
+    pub sample_rate: f64,
+    pub length: f64,
+    pub frames: Vec<PoseFrame>,
+}
+
+fn to_vec3(values: Vec<f64>) -> Option<[f64; 3]> {
+    values.try_into().ok()
+}
+
+/// Pose of every animated transform at `time`
+pub fn sample_clip(clip: &AnimationClipData, time: f64) -> PoseFrame {
+    let mut poses: BTreeMap<String, TransformPose> = BTreeMap::new();
+
+    for track in &clip.tracks {
+        let Some(values) = evaluate_track(track, time) else {
+            continue;
+        };
+        let pose = poses
+            .entry(track.path.clone())
+            .or_insert_with(|| TransformPose {
+                path: track.path.clone(),
+                ..Default::default()
+            });
+        match (track.kind, track.attribute.as_str()) {
+            (TrackKind::Vector3, "m_LocalPosition") => pose.local_position = to_vec3(values),
+            (TrackKind::Vector3, "m_LocalScale") => pose.local_scale = to_vec3(values),
+            (TrackKind::Vector3, _) => pose.local_euler_angles = to_vec3(values),
+            (TrackKind::Quaternion, _) => pose.local_rotation = values.try_into().ok(),
+            (TrackKind::Float, attribute) => {
+                pose.floats.insert(attribute.to_string(), values[0]);
+            }
+        }
+    }
+
+    for track in &clip.sprite_tracks {
+        let Some(keyframe) = evaluate_sprite_track(track, time) else {
+            continue;
+        };
+        let pose = poses
+            .entry(track.path.clone())
+            .or_insert_with(|| TransformPose {
+                path: track.path.clone(),
+                ..Default::default()
+            });
+        pose.sprite = Some(keyframe.clone());
+    }
+
+    PoseFrame {
+        time,
+        transforms: poses.into_values().collect(),
+    }
+}
+
+/// Sample times from start to stop (inclusive) at the given frame rate
+pub fn frame_times(clip: &AnimationClipData, fps: f64) -> Vec<f64> {
+    if fps <= 0.0 || clip.length <= 0.0 {
+        return vec![clip.start_time];
+    }
+    let count = ((clip.length * fps).round() as usize + 1).min(MAX_SAMPLED_FRAMES);
+    (0..count)
+        .map(|i| (clip.start_time + i as f64 / fps).min(clip.stop_time))
+        .collect()
+}
+
+#[tauri::command]
+pub async fn sample_animation_clip(
+    project_path: String,
+    clip_path: Option<String>,
+    clip_guid: Option<String>,
+    times: Option<Vec<f64>>,
+    fps: Option<f64>,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<ClipSamples, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let path = index.resolve_path_or_guid(clip_path.as_deref(), clip_guid.as_deref())?;
+    let clip = load_animation_clip(&path, &index, false)?;
+
+    // Không truyền times thì lấy mẫu theo fps hoặc m_SampleRate của clip
+    let times = match times {
+        Some(times) => times,
+        None => frame_times(&clip, fps.unwrap_or(clip.sample_rate)),
+    };
+    let frames = times.iter().map(|t| sample_clip(&clip, *t)).collect();
+
+    Ok(ClipSamples {
+        clip_name: clip.name.clone(),
+        sample_rate: clip.sample_rate,
+        length: clip.length,
+        frames,
+    })
+}
@@ -0,0 +1,503 @@
+use crate::unity::document::{parse_unity_documents, UnityDocument, CLASS_MONO_BEHAVIOUR};
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use crate::unity::mono_script::{resolve_scripts, ScriptInfo};
+use crate::unity::reference::{resolve_references, LocalObject};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use tauri::State;
+
+// Field chung của mọi MonoBehaviour, không phải dữ liệu game
+const ENGINE_FIELDS: &[&str] = &[
+    "m_CorrespondingSourceObject",
+    "m_PrefabInstance",
+    "m_PrefabAsset",
+    "m_GameObject",
+    "m_Enabled",
+    "m_EditorHideFlags",
+    "m_Script",
+    "m_Name",
+    "m_EditorClassIdentifier",
+    "m_ObjectHideFlags",
+];
+
+// Cột khoá luôn đứng đầu bảng, các field của script theo sau; tiền tố `_` để không
+// trùng với field của script (nhiều class có field `index`, `name`)
+const ROW_ID: &str = "_rowId";
+const PARENT_ID: &str = "_parentId";
+const INDEX: &str = "_index";
+const ASSET: &str = "_asset";
+const NAME: &str = "_name";
+const KEY_COLUMNS: &[&str] = &[ROW_ID, PARENT_ID, INDEX, ASSET, NAME];
+
+// Key dùng làm id của hero trong info.json, theo thứ tự ưu tiên
+const HERO_ID_FIELDS: &[&str] = &["unit_id", "unitId", "id", "heroId"];
+
+/// Rows of one ScriptableObject class, or of an array field of that class
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTable {
+    /// `Namespace.ClassName`, or `Namespace.ClassName.field` for a child table
+    pub name: String,
+    pub script_guid: String,
+    pub script: Option<ScriptInfo>,
+    /// Table whose rows own this one (`_parentId` column)
+    pub parent: Option<String>,
+    pub columns: Vec<String>,
+    pub rows: Vec<Map<String, Value>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataTableSummary {
+    pub name: String,
+    pub parent: Option<String>,
+    pub row_count: usize,
+    pub column_count: usize,
+    pub json_path: Option<String>,
+    pub csv_path: Option<String>,
+}
+
+// Object mảng (vd. stats theo level) được tách thành bảng con; mảng reference
+// (`List<Sprite>`...) là giá trị của một ô, không phải bảng
+fn is_object_array(value: &Value) -> bool {
+    matches!(value, Value::Array(items) if !items.is_empty()
+        && items.iter().all(|item| item.as_object().is_some_and(|map| !map.contains_key("fileID"))))
+}
+
+// Reference đã resolve được ghi ra bằng tên asset/object thay vì {fileID, guid}
+fn reference_label(map: &Map<String, Value>) -> Option<Value> {
+    if map.get("fileID")?.as_i64() == Some(0) {
+        return Some(Value::Null);
+    }
+    let target = map.get("target")?;
+    let label = target
+        .get("path")
+        .filter(|p| !p.is_null())
+        .or_else(|| target.get("name").filter(|n| !n.is_null()))?;
+    Some(label.clone())
+}
+
+struct TableBuilder {
+    tables: BTreeMap<String, DataTable>,
+}
+
+impl TableBuilder {
+    fn table(
+        &mut self,
+        name: &str,
+        guid: &str,
+        script: Option<&ScriptInfo>,
+        parent: Option<&str>,
+    ) -> &mut DataTable {
+        self.tables
+            .entry(name.to_string())
+            .or_insert_with(|| DataTable {
+                name: name.to_string(),
+                script_guid: guid.to_string(),
+                script: script.cloned(),
+                parent: parent.map(str::to_string),
+                columns: Vec::new(),
+                rows: Vec::new(),
+            })
+    }
+
+    // Làm phẳng object lồng nhau thành cột `a.b.c`
+    fn flatten(
+        &mut self,
+        table: &str,
+        guid: &str,
+        row_id: &str,
+        prefix: &str,
+        value: &Value,
+        row: &mut Map<String, Value>,
+    ) {
+        match value {
+            Value::Object(map) => {
+                if let Some(label) = reference_label(map) {
+                    row.insert(prefix.to_string(), label);
+                    return;
+                }
+                for (key, child) in map {
+                    let column = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    self.flatten(table, guid, row_id, &column, child, row);
+                }
+            }
+            Value::Array(items) if is_object_array(value) => {
+                let child_table = format!("{}.{}", table, prefix);
+                for (i, item) in items.iter().enumerate() {
+                    let child_id = format!("{}/{}[{}]", row_id, prefix, i);
+                    let mut child_row = Map::new();
+                    child_row.insert(ROW_ID.to_string(), Value::String(child_id.clone()));
+                    child_row.insert(PARENT_ID.to_string(), Value::String(row_id.to_string()));
+                    child_row.insert(INDEX.to_string(), Value::from(i));
+                    self.flatten(&child_table, guid, &child_id, "", item, &mut child_row);
+                    self.push_row(&child_table, guid, Some(table), child_row);
+                }
+            }
+            Value::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| match item {
+                        Value::Object(map) => reference_label(map).unwrap_or(item.clone()),
+                        _ => item.clone(),
+                    })
+                    .collect();
+                row.insert(prefix.to_string(), Value::Array(items));
+            }
+            other => {
+                row.insert(prefix.to_string(), other.clone());
+            }
+        }
+    }
+
+    fn push_row(&mut self, name: &str, guid: &str, parent: Option<&str>, row: Map<String, Value>) {
+        let table = self.table(name, guid, None, parent);
+        for key in row.keys() {
+            if !table.columns.contains(key) {
+                table.columns.push(key.clone());
+            }
+        }
+        table.rows.push(row);
+    }
+}
+
+// Tên bảng theo class của script, GUID nếu không resolve được
+fn table_name(guid: &str, script: Option<&ScriptInfo>) -> String {
+    let class_name = script.and_then(|s| s.class_name.as_ref());
+    match (script.and_then(|s| s.namespace.as_ref()), class_name) {
+        (Some(ns), Some(class_name)) => format!("{}.{}", ns, class_name),
+        (None, Some(class_name)) => class_name.clone(),
+        _ => guid.to_string(),
+    }
+}
+
+// ScriptableObject: MonoBehaviour không gắn vào GameObject nào
+fn data_documents(content: &str) -> Vec<UnityDocument> {
+    parse_unity_documents(content)
+        .into_iter()
+        .filter(|d| d.class_id == CLASS_MONO_BEHAVIOUR && d.error.is_none())
+        .filter(|d| d.reference("m_GameObject").unwrap_or(0) == 0)
+        .collect()
+}
+
+/// Group every ScriptableObject `.asset` of the project by script class
+pub fn extract_data_tables(index: &GuidIndex) -> Vec<DataTable> {
+    let mut assets: Vec<(String, Vec<UnityDocument>)> = Vec::new();
+    for (_, entry) in index.entries() {
+        if !entry.path.ends_with(".asset") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(index.root().join(&entry.path)) else {
+            continue;
+        };
+        if !content.contains("--- !u!114 ") {
+            continue;
+        }
+        let documents = data_documents(&content);
+        if !documents.is_empty() {
+            assets.push((entry.path.clone(), documents));
+        }
+    }
+    assets.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let script_guid = |d: &UnityDocument| {
+        d.body
+            .get("m_Script")
+            .and_then(|s| s.get("guid"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let guids: Vec<String> = assets
+        .iter()
+        .flat_map(|(_, docs)| docs.iter().map(script_guid))
+        .collect();
+    let scripts = resolve_scripts(guids.iter().map(String::as_str), index);
+
+    let mut builder = TableBuilder {
+        tables: BTreeMap::new(),
+    };
+    for (path, documents) in &assets {
+        let locals: HashMap<i64, LocalObject> = documents
+            .iter()
+            .map(|d| {
+                let name = d.body.get("m_Name").and_then(Value::as_str);
+                (
+                    d.file_id,
+                    LocalObject {
+                        type_name: d.type_name.clone(),
+                        name: name.map(str::to_string),
+                    },
+                )
+            })
+            .collect();
+        for document in documents {
+            let guid = script_guid(document);
+            let script = scripts.get(&guid);
+            let name = table_name(&guid, script);
+            builder.table(&name, &guid, script, None);
+
+            let row_id = format!("{}#{}", path, document.file_id);
+            let mut row = Map::new();
+            row.insert(ROW_ID.to_string(), Value::String(row_id.clone()));
+            row.insert(ASSET.to_string(), Value::String(path.clone()));
+            row.insert(
+                NAME.to_string(),
+                document.body.get("m_Name").cloned().unwrap_or_default(),
+            );
+            let fields: Map<String, Value> = document
+                .body
+                .as_object()
+                .map(|body| {
+                    body.iter()
+                        .filter(|(k, _)| !ENGINE_FIELDS.contains(&k.as_str()))
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let fields = resolve_references(&Value::Object(fields), &locals, index);
+            builder.flatten(&name, &guid, &row_id, "", &fields, &mut row);
+            builder.push_row(&name, &guid, None, row);
+        }
+    }
+    let mut tables: Vec<DataTable> = builder.tables.into_values().collect();
+    for table in &mut tables {
+        table.columns.sort_by_key(|c| {
+            KEY_COLUMNS
+                .iter()
+                .position(|k| k == c)
+                .unwrap_or(KEY_COLUMNS.len())
+        });
+    }
+    tables
+}
+
+// Object JSON của một dòng: field của script, cộng `name` của asset nếu script không có field đó
+fn without_keys(row: &Map<String, Value>) -> Map<String, Value> {
+    let mut object: Map<String, Value> = row
+        .iter()
+        .filter(|(k, _)| !KEY_COLUMNS.contains(&k.as_str()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    if let Some(name) = row.get(NAME) {
+        object
+            .entry("name".to_string())
+            .or_insert_with(|| name.clone());
+    }
+    object
+}
+
+// Gắn lại các bảng con (vd. `levels`) vào object cha
+fn nest_children(tables: &[DataTable], table: &DataTable, row_id: &str) -> Map<String, Value> {
+    let mut nested = Map::new();
+    for child in tables
+        .iter()
+        .filter(|t| t.parent.as_deref() == Some(table.name.as_str()))
+    {
+        let field = child.name[table.name.len() + 1..].to_string();
+        let items: Vec<Value> = child
+            .rows
+            .iter()
+            .filter(|r| r.get(PARENT_ID).and_then(Value::as_str) == Some(row_id))
+            .map(|r| {
+                let child_id = r.get(ROW_ID).and_then(Value::as_str).unwrap_or_default();
+                let mut item = without_keys(r);
+                item.extend(nest_children(tables, child, child_id));
+                Value::Object(item)
+            })
+            .collect();
+        if !items.is_empty() {
+            nested.insert(field, Value::Array(items));
+        }
+    }
+    nested
+}
+
+fn csv_cell(value: Option<&Value>) -> String {
+    let text = match value {
+        None | Some(Value::Null) => return String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+/// Table as CSV, one column per flattened field
+pub fn table_to_csv(table: &DataTable) -> String {
+    let mut csv = table
+        .columns
+        .iter()
+        .map(|c| csv_cell(Some(&Value::String(c.clone()))))
+        .collect::<Vec<_>>()
+        .join(",");
+    csv.push('\n');
+    for row in &table.rows {
+        let cells: Vec<String> = table.columns.iter().map(|c| csv_cell(row.get(c))).collect();
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// `public/json/hero/info.json`: hero rows keyed by unit id (or asset name),
+/// with child tables such as per-level stats nested back in
+pub fn hero_info(tables: &[DataTable], table: &DataTable) -> Map<String, Value> {
+    let mut info = Map::new();
+    for row in &table.rows {
+        let key = HERO_ID_FIELDS
+            .iter()
+            .find_map(|f| row.get(*f).filter(|v| !v.is_null()))
+            .or_else(|| row.get(NAME))
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .unwrap_or_default();
+        let row_id = row.get(ROW_ID).and_then(Value::as_str).unwrap_or_default();
+        let mut hero = without_keys(row);
+        hero.extend(nest_children(tables, table, row_id));
+        info.insert(key, Value::Object(hero));
+    }
+    info
+}
+
+fn file_name(table: &str) -> String {
+    table.replace(['/', '\\', ':', '<', '>', '|', '?', '*', '"'], "_")
+}
+
+#[tauri::command]
+pub async fn export_data_tables(
+    project_path: String,
+    output_dir: Option<String>,
+    hero_class: Option<String>,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<Vec<DataTableSummary>, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let tables = extract_data_tables(&index);
+
+    let output_dir = output_dir.map(|d| Path::new(&d).to_path_buf());
+    if let Some(dir) = &output_dir {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+
+    let mut summaries = Vec::new();
+    for table in &tables {
+        let mut summary = DataTableSummary {
+            name: table.name.clone(),
+            parent: table.parent.clone(),
+            row_count: table.rows.len(),
+            column_count: table.columns.len(),
+            json_path: None,
+            csv_path: None,
+        };
+        if let Some(dir) = &output_dir {
+            let json_path = dir.join(format!("{}.json", file_name(&table.name)));
+            let json = serde_json::to_string_pretty(table)
+                .map_err(|e| format!("Failed to serialize table {}: {}", table.name, e))?;
+            fs::write(&json_path, json)
+                .map_err(|e| format!("Failed to write {}: {}", json_path.display(), e))?;
+            let csv_path = dir.join(format!("{}.csv", file_name(&table.name)));
+            fs::write(&csv_path, table_to_csv(table))
+                .map_err(|e| format!("Failed to write {}: {}", csv_path.display(), e))?;
+            summary.json_path = Some(json_path.to_string_lossy().to_string());
+            summary.csv_path = Some(csv_path.to_string_lossy().to_string());
+        }
+        summaries.push(summary);
+    }
+
+    // Class hero có thể là `HeroData` hoặc `Kgc.Data.HeroData`
+    if let (Some(dir), Some(hero_class)) = (&output_dir, &hero_class) {
+        let table = tables
+            .iter()
+            .find(|t| {
+                t.parent.is_none()
+                    && (t.name == *hero_class || t.name.ends_with(&format!(".{}", hero_class)))
+            })
+            .ok_or_else(|| format!("No data table found for class {}", hero_class))?;
+        let hero_dir = dir.join("hero");
+        fs::create_dir_all(&hero_dir)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+        let json = serde_json::to_string_pretty(&hero_info(&tables, table))
+            .map_err(|e| format!("Failed to serialize hero info: {}", e))?;
+        fs::write(hero_dir.join("info.json"), json)
+            .map_err(|e| format!("Failed to write hero info: {}", e))?;
+    }
+    Ok(summaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Dòng gốc như extract_data_tables tạo ra, field của script đã resolve reference
+    fn build(fields: Value) -> Vec<DataTable> {
+        let mut builder = TableBuilder {
+            tables: BTreeMap::new(),
+        };
+        let mut row = Map::new();
+        row.insert(ROW_ID.to_string(), json!("Assets/Hero.asset#11400000"));
+        row.insert(ASSET.to_string(), json!("Assets/Hero.asset"));
+        row.insert(NAME.to_string(), json!("Hero"));
+        builder.flatten(
+            "Hero",
+            "g",
+            "Assets/Hero.asset#11400000",
+            "",
+            &fields,
+            &mut row,
+        );
+        builder.push_row("Hero", "g", None, row);
+        builder.tables.into_values().collect()
+    }
+
+    #[test]
+    fn reference_arrays_stay_in_one_cell() {
+        let tables = build(json!({
+            "sprites": [
+                {"fileID": 21300000, "guid": "aaaa", "type": 3, "target": {"path": "Assets/a.png"}},
+                {"fileID": 0},
+            ],
+            "levels": [{"hp": 10}, {"hp": 20}],
+        }));
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Hero", "Hero.levels"]);
+        let row = &tables[0].rows[0];
+        assert_eq!(row["sprites"], json!(["Assets/a.png", null]));
+        assert!(!tables[0].columns.contains(&String::new()));
+        assert_eq!(tables[1].rows[1]["hp"], json!(20));
+        assert_eq!(tables[1].rows[1][INDEX], json!(1));
+    }
+
+    #[test]
+    fn script_fields_do_not_clash_with_key_columns() {
+        let tables = build(json!({
+            "index": 7,
+            "name": "Knight",
+            "levels": [{"index": 3, "hp": 10}],
+        }));
+        let row = &tables[0].rows[0];
+        assert_eq!(row["index"], json!(7));
+        assert_eq!(row["name"], json!("Knight"));
+        assert_eq!(row[NAME], json!("Hero"));
+        let level = &tables[1].rows[0];
+        assert_eq!((&level["index"], &level[INDEX]), (&json!(3), &json!(0)));
+
+        let info = hero_info(&tables, &tables[0]);
+        let hero = info["Hero"].as_object().unwrap();
+        assert_eq!(hero["name"], json!("Knight"));
+        assert_eq!(hero["index"], json!(7));
+        assert_eq!(hero["levels"], json!([{"index": 3, "hp": 10}]));
+    }
+}
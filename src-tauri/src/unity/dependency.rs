@@ -0,0 +1,274 @@
+use crate::unity::document::parse_unity_documents;
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+pub type DependencyState = Arc<Mutex<HashMap<String, Arc<DependencyGraph>>>>;
+
+pub fn create_dependency_state() -> DependencyState {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+// Ảnh, âm thanh... không ai tham chiếu thì coi là không dùng
+const ART_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "tga", "psd", "wav", "mp3", "ogg"];
+
+/// `guid -> guids it references` and the reverse, built from every YAML asset
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    dependencies: HashMap<String, BTreeSet<String>>,
+    dependents: HashMap<String, BTreeSet<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyEntry {
+    pub guid: String,
+    /// Project-relative path, `None` for built-in or missing assets
+    pub path: Option<String>,
+    /// Hops from the asset that was asked about (1 = direct reference)
+    pub depth: usize,
+}
+
+// Chỉ đọc file text Unity YAML (prefab, scene, anim, controller, mat, asset...)
+fn is_unity_yaml(path: &Path) -> bool {
+    let Ok(mut file) = File::open(path) else {
+        return false;
+    };
+    let mut head = [0u8; 5];
+    file.read_exact(&mut head).is_ok() && &head == b"%YAML"
+}
+
+fn collect_guids(value: &Value, guids: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(guid) = map.get("guid").and_then(Value::as_str) {
+                if map.contains_key("fileID") && !guid.is_empty() {
+                    guids.insert(guid.to_string());
+                }
+            }
+            map.values().for_each(|v| collect_guids(v, guids));
+        }
+        Value::Array(items) => items.iter().for_each(|v| collect_guids(v, guids)),
+        _ => {}
+    }
+}
+
+impl DependencyGraph {
+    pub fn build(index: &GuidIndex) -> DependencyGraph {
+        let mut graph = DependencyGraph::default();
+        for (guid, entry) in index.entries() {
+            let path = index.root().join(&entry.path);
+            if !path.is_file() || !is_unity_yaml(&path) {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let mut guids = BTreeSet::new();
+            for document in parse_unity_documents(&content) {
+                collect_guids(&document.body, &mut guids);
+            }
+            guids.remove(guid);
+            for dependency in &guids {
+                graph
+                    .dependents
+                    .entry(dependency.clone())
+                    .or_default()
+                    .insert(guid.clone());
+            }
+            if !guids.is_empty() {
+                graph.dependencies.insert(guid.clone(), guids);
+            }
+        }
+        graph
+    }
+
+    // BFS theo một chiều của đồ thị; `recursive = false` chỉ lấy tham chiếu trực tiếp
+    fn walk(
+        edges: &HashMap<String, BTreeSet<String>>,
+        guid: &str,
+        recursive: bool,
+        index: &GuidIndex,
+    ) -> Vec<DependencyEntry> {
+        let mut visited: BTreeSet<&str> = BTreeSet::from([guid]);
+        let mut queue = VecDeque::from([(guid, 0)]);
+        let mut result = Vec::new();
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth > 0 && !recursive {
+                break;
+            }
+            for next in edges.get(current).into_iter().flatten() {
+                if !visited.insert(next.as_str()) {
+                    continue;
+                }
+                result.push(DependencyEntry {
+                    guid: next.clone(),
+                    path: index.entry(next).map(|e| e.path.clone()),
+                    depth: depth + 1,
+                });
+                queue.push_back((next.as_str(), depth + 1));
+            }
+        }
+        result
+    }
+
+    /// Assets `guid` uses; with `recursive` the full closure (everything needed to extract it)
+    pub fn dependencies(
+        &self,
+        guid: &str,
+        recursive: bool,
+        index: &GuidIndex,
+    ) -> Vec<DependencyEntry> {
+        Self::walk(&self.dependencies, guid, recursive, index)
+    }
+
+    /// Assets that use `guid`; with `recursive` also the assets that use those
+    pub fn dependents(
+        &self,
+        guid: &str,
+        recursive: bool,
+        index: &GuidIndex,
+    ) -> Vec<DependencyEntry> {
+        Self::walk(&self.dependents, guid, recursive, index)
+    }
+
+    /// Assets with one of `extensions` that nothing references
+    pub fn unused(&self, index: &GuidIndex, extensions: &[String]) -> Vec<DependencyEntry> {
+        let mut unused: Vec<DependencyEntry> = index
+            .entries()
+            .filter(|(guid, _)| !self.dependents.contains_key(*guid))
+            .filter(|(_, entry)| {
+                let extension = entry.path.rsplit('.').next().unwrap_or_default();
+                extensions.iter().any(|e| e.eq_ignore_ascii_case(extension))
+            })
+            .map(|(guid, entry)| DependencyEntry {
+                guid: guid.clone(),
+                path: Some(entry.path.clone()),
+                depth: 0,
+            })
+            .collect();
+        unused.sort_by(|a, b| a.path.cmp(&b.path));
+        unused
+    }
+}
+
+pub(crate) fn project_graph(
+    state: &DependencyState,
+    index: &GuidIndex,
+    reload: bool,
+) -> Result<Arc<DependencyGraph>, String> {
+    let key = index.root().to_string_lossy().to_string();
+    if !reload {
+        let graphs = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(graph) = graphs.get(&key) {
+            return Ok(graph.clone());
+        }
+    }
+    // Đọc mọi file YAML của project, không giữ lock trong lúc build
+    let graph = Arc::new(DependencyGraph::build(index));
+    let mut graphs = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if reload {
+        graphs.insert(key, graph.clone());
+        return Ok(graph);
+    }
+    Ok(graphs.entry(key).or_insert(graph).clone())
+}
+
+/// Drop the cached graph of every project where an asset changed
+pub fn invalidate_dependency_graph(state: &DependencyState, event: &notify::Event) {
+    if matches!(event.kind, notify::EventKind::Access(_)) {
+        return;
+    }
+    // .meta và ảnh/âm thanh không chứa tham chiếu; chỉ Assets/ có trong GUID index
+    let changed: Vec<&Path> = event
+        .paths
+        .iter()
+        .map(|p| p.as_path())
+        .filter(|p| {
+            let extension = p
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            extension != "meta" && !ART_EXTENSIONS.contains(&extension.as_str())
+        })
+        .collect();
+    if changed.is_empty() {
+        return;
+    }
+    let Ok(mut graphs) = state.lock() else {
+        return;
+    };
+    graphs.retain(|root, _| {
+        let assets_dir = Path::new(root).join("Assets");
+        !changed.iter().any(|p| p.starts_with(&assets_dir))
+    });
+}
+
+// Tham số là path hoặc GUID của asset
+pub(crate) fn target_guid(
+    index: &GuidIndex,
+    asset_path: Option<String>,
+    asset_guid: Option<String>,
+) -> Result<String, String> {
+    if let Some(guid) = asset_guid.filter(|g| !g.is_empty()) {
+        return Ok(guid);
+    }
+    let path = index.resolve_path_or_guid(asset_path.as_deref(), None)?;
+    index
+        .guid_for_path(&path)
+        .cloned()
+        .ok_or_else(|| format!("Asset is not in the GUID index: {}", path.display()))
+}
+
+#[tauri::command]
+pub async fn asset_dependencies(
+    project_path: String,
+    asset_path: Option<String>,
+    asset_guid: Option<String>,
+    recursive: Option<bool>,
+    reload: Option<bool>,
+    guid_index: State<'_, GuidIndexState>,
+    dependency_graph: State<'_, DependencyState>,
+) -> Result<Vec<DependencyEntry>, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let guid = target_guid(&index, asset_path, asset_guid)?;
+    let graph = project_graph(&dependency_graph, &index, reload.unwrap_or(false))?;
+    Ok(graph.dependencies(&guid, recursive.unwrap_or(true), &index))
+}
+
+#[tauri::command]
+pub async fn asset_references(
+    project_path: String,
+    asset_path: Option<String>,
+    asset_guid: Option<String>,
+    recursive: Option<bool>,
+    reload: Option<bool>,
+    guid_index: State<'_, GuidIndexState>,
+    dependency_graph: State<'_, DependencyState>,
+) -> Result<Vec<DependencyEntry>, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let guid = target_guid(&index, asset_path, asset_guid)?;
+    let graph = project_graph(&dependency_graph, &index, reload.unwrap_or(false))?;
+    Ok(graph.dependents(&guid, recursive.unwrap_or(false), &index))
+}
+
+#[tauri::command]
+pub async fn unused_assets(
+    project_path: String,
+    extensions: Option<Vec<String>>,
+    reload: Option<bool>,
+    guid_index: State<'_, GuidIndexState>,
+    dependency_graph: State<'_, DependencyState>,
+) -> Result<Vec<DependencyEntry>, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let graph = project_graph(&dependency_graph, &index, reload.unwrap_or(false))?;
+    let extensions =
+        extensions.unwrap_or_else(|| ART_EXTENSIONS.iter().map(|e| e.to_string()).collect());
+    Ok(graph.unused(&index, &extensions))
+}
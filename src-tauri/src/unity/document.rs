@@ -0,0 +1,228 @@
+use crate::unity::error::{deserialize_document, UnityParseError};
+use crate::utils::yaml_to_json;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use unity_yaml_rust::yaml::YamlLoader;
+
+pub const CLASS_GAME_OBJECT: i64 = 1;
+pub const CLASS_TRANSFORM: i64 = 4;
+pub const CLASS_ANIMATOR: i64 = 95;
+pub const CLASS_MONO_BEHAVIOUR: i64 = 114;
+pub const CLASS_PARTICLE_SYSTEM: i64 = 198;
+pub const CLASS_SPRITE_RENDERER: i64 = 212;
+pub const CLASS_RECT_TRANSFORM: i64 = 224;
+pub const CLASS_PREFAB_INSTANCE: i64 = 1001;
+pub const CLASS_SCENE_ROOTS: i64 = 1660057539;
+
+// Bảng class ID -> tên type của Unity (https://docs.unity3d.com/Manual/ClassIDReference.html)
+const UNITY_CLASSES: &[(i64, &str)] = &[
+    (1, "GameObject"),
+    (4, "Transform"),
+    (20, "Camera"),
+    (21, "Material"),
+    (23, "MeshRenderer"),
+    (28, "Texture2D"),
+    (29, "OcclusionCullingSettings"),
+    (33, "MeshFilter"),
+    (48, "Shader"),
+    (50, "Rigidbody2D"),
+    (54, "Rigidbody"),
+    (58, "CircleCollider2D"),
+    (60, "PolygonCollider2D"),
+    (61, "BoxCollider2D"),
+    (64, "MeshCollider"),
+    (65, "BoxCollider"),
+    (66, "CompositeCollider2D"),
+    (68, "EdgeCollider2D"),
+    (70, "CapsuleCollider2D"),
+    (74, "AnimationClip"),
+    (81, "AudioListener"),
+    (82, "AudioSource"),
+    (83, "AudioClip"),
+    (91, "AnimatorController"),
+    (95, "Animator"),
+    (96, "TrailRenderer"),
+    (102, "TextMesh"),
+    (104, "RenderSettings"),
+    (108, "Light"),
+    (111, "Animation"),
+    (114, "MonoBehaviour"),
+    (115, "MonoScript"),
+    (120, "LineRenderer"),
+    (124, "FlareLayer"),
+    (135, "SphereCollider"),
+    (136, "CapsuleCollider"),
+    (157, "LightmapSettings"),
+    (196, "NavMeshSettings"),
+    (198, "ParticleSystem"),
+    (199, "ParticleSystemRenderer"),
+    (206, "BlendTree"),
+    (210, "SortingGroup"),
+    (212, "SpriteRenderer"),
+    (213, "Sprite"),
+    (221, "AnimatorOverrideController"),
+    (222, "CanvasRenderer"),
+    (223, "Canvas"),
+    (224, "RectTransform"),
+    (225, "CanvasGroup"),
+    (331, "SpriteMask"),
+    (1001, "PrefabInstance"),
+    (1101, "AnimatorStateTransition"),
+    (1102, "AnimatorState"),
+    (1107, "AnimatorStateMachine"),
+    (1109, "AnimatorTransition"),
+    (483693784, "TilemapRenderer"),
+    (687078895, "SpriteAtlas"),
+    (850595691, "LightingSettings"),
+    (1660057539, "SceneRoots"),
+    (1839735485, "Tilemap"),
+];
+
+/// Unity type name of a class ID, if the class is known
+pub fn class_name(class_id: i64) -> Option<&'static str> {
+    UNITY_CLASSES
+        .iter()
+        .find(|(id, _)| *id == class_id)
+        .map(|(_, name)| *name)
+}
+
+/// Một document `--- !u!<class_id> &<file_id>` của file Unity YAML, đã chuyển sang JSON
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnityDocument {
+    pub class_id: i64,
+    pub file_id: i64,
+    /// `--- !u!4 &123 stripped`: placeholder for an object owned by a PrefabInstance
+    pub stripped: bool,
+    /// Key above the body (`Transform:`), or the registry name when the body is missing
+    pub type_name: String,
+    /// Properties of the object, without the outer `TypeName:` key
+    pub body: Value,
+    /// Set when the document could not be read; `body` is then `Null`
+    #[serde(skip)]
+    pub error: Option<UnityParseError>,
+}
+
+impl UnityDocument {
+    pub fn failed(class_id: i64, file_id: i64, error: UnityParseError) -> Self {
+        UnityDocument {
+            class_id,
+            file_id,
+            stripped: false,
+            type_name: class_name(class_id).unwrap_or_default().to_string(),
+            body: Value::Null,
+            error: Some(error),
+        }
+    }
+
+    /// Typed view of the body, deserialized on demand
+    pub fn view<T: DeserializeOwned>(&self) -> Result<T, UnityParseError> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        deserialize_document(self.file_id, &self.body).map_err(|e| match e {
+            UnityParseError::SchemaMismatch {
+                file_id,
+                path,
+                message,
+            } => UnityParseError::SchemaMismatch {
+                file_id,
+                path: if path.is_empty() {
+                    self.type_name.clone()
+                } else {
+                    format!("{}.{}", self.type_name, path)
+                },
+                message,
+            },
+            e => e,
+        })
+    }
+
+    /// `fileID` of a `{fileID: ...}` property such as `m_GameObject`
+    pub fn reference(&self, key: &str) -> Option<i64> {
+        self.body.get(key)?.get("fileID")?.as_i64()
+    }
+}
+
+// Tách file Unity YAML thành từng document; document lỗi được giữ lại kèm `error`
+pub(crate) fn parse_unity_documents(content: &str) -> Vec<UnityDocument> {
+    let mut documents = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].starts_with("--- !u!") {
+            let header = lines[i];
+            let bad_header = UnityParseError::BadHeader {
+                line: i + 1,
+                header: header.to_string(),
+            };
+            let parts: Vec<&str> = header.split_whitespace().collect();
+            let stripped = parts.len() == 4 && parts[3] == "stripped";
+            if parts.len() != 3 && !stripped {
+                documents.push(UnityDocument::failed(0, 0, bad_header));
+                i += 1;
+                continue;
+            }
+            let class_id: i64 = parts[1].trim_start_matches("!u!").parse().unwrap_or(0);
+            let id_str = parts[2].trim_start_matches('&');
+            let id: i64 = match id_str.parse() {
+                Ok(num) => num,
+                Err(_) => {
+                    documents.push(UnityDocument::failed(class_id, 0, bad_header));
+                    i += 1;
+                    continue;
+                }
+            };
+            let mut body_lines = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].starts_with("--- !u!") {
+                body_lines.push(lines[i]);
+                i += 1;
+            }
+            let body = body_lines.join("\n");
+            let yaml_error = |message: String| UnityParseError::Yaml {
+                file_id: id,
+                message,
+            };
+            let docs = match YamlLoader::load_from_str(&body) {
+                Ok(d) => d,
+                Err(e) => {
+                    documents.push(UnityDocument::failed(
+                        class_id,
+                        id,
+                        yaml_error(e.to_string()),
+                    ));
+                    continue;
+                }
+            };
+            if docs.is_empty() {
+                continue;
+            }
+            let yaml = &docs[0];
+            let json = match yaml_to_json(yaml) {
+                Ok(j) => j,
+                Err(e) => {
+                    documents.push(UnityDocument::failed(class_id, id, yaml_error(e)));
+                    continue;
+                }
+            };
+            // Document có dạng `TypeName: { ... }`
+            let (type_name, body) = match json {
+                Value::Object(map) if map.len() == 1 => map.into_iter().next().unwrap_or_default(),
+                other => (class_name(class_id).unwrap_or_default().to_string(), other),
+            };
+            documents.push(UnityDocument {
+                class_id,
+                file_id: id,
+                stripped,
+                type_name,
+                body,
+                error: None,
+            });
+        } else {
+            i += 1;
+        }
+    }
+    documents
+}
@@ -0,0 +1,85 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Lỗi khi đọc file Unity YAML (prefab, scene, sprite asset...)
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum UnityParseError {
+    #[error("Invalid document header at line {line}: {header}")]
+    BadHeader { line: usize, header: String },
+    #[error("Invalid YAML in document &{file_id}: {message}")]
+    Yaml { file_id: i64, message: String },
+    #[error("Unknown class ID {class_id} ({type_name}) in document &{file_id}")]
+    UnknownClassId {
+        class_id: i64,
+        file_id: i64,
+        type_name: String,
+    },
+    #[error("Schema mismatch in document &{file_id} at {path}: {message}")]
+    SchemaMismatch {
+        file_id: i64,
+        path: String,
+        message: String,
+    },
+    #[error("GUID not found in project: {guid}")]
+    MissingGuid { guid: String },
+    #[error("Texture not found for sprite {sprite_guid}: {message}")]
+    MissingTexture {
+        sprite_guid: String,
+        texture_guid: Option<String>,
+        message: String,
+    },
+    #[error("Failed to read source prefab {guid} ({path}): {message}")]
+    UnreadablePrefab {
+        guid: String,
+        path: String,
+        message: String,
+    },
+    #[error("Recursive prefab instance skipped at depth {depth}: {guid}")]
+    RecursivePrefab { guid: String, depth: usize },
+}
+
+/// Lỗi gắn với một document cụ thể trong file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentDiagnostic {
+    pub file_id: i64,
+    pub class_id: i64,
+    /// GameObject owning the document, when the document says so
+    pub game_object_id: Option<i64>,
+    pub error: UnityParseError,
+}
+
+/// Deserialize a document body, reporting the field path that does not match `T`
+pub(crate) fn deserialize_document<T: DeserializeOwned>(
+    file_id: i64,
+    value: &Value,
+) -> Result<T, UnityParseError> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        // Path gốc được in là "."
+        let mut path = match e.path().to_string() {
+            root if root == "." => String::new(),
+            path => path,
+        };
+        let message = e.inner().to_string();
+        // Thiếu field: path dừng ở object cha, thêm tên field
+        if let Some(field) = message.strip_prefix("missing field `") {
+            let field = field.split('`').next().unwrap_or_default();
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(field);
+        }
+        UnityParseError::SchemaMismatch {
+            file_id,
+            path,
+            message,
+        }
+    })
+}
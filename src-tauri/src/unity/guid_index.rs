@@ -0,0 +1,590 @@
+use glob::glob;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tauri::State;
+
+// Index được lưu cạnh project: <project>/.kgc-toolkit/guid-index.json
+pub(crate) const INDEX_DIR: &str = ".kgc-toolkit";
+const INDEX_FILE: &str = "guid-index.json";
+const INDEX_VERSION: u32 = 2;
+
+// Watcher báo hàng nghìn .meta khi export/checkout: chỉ ghi index khi đã yên một lúc
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+// Các event .meta liên tiếp được áp dụng chung một lần
+const APPLY_DELAY: Duration = Duration::from_millis(200);
+
+const SPRITE_ATLAS_EXTENSION: &str = ".spriteatlas";
+
+fn is_sprite_atlas(path: &str) -> bool {
+    path.to_lowercase().ends_with(SPRITE_ATLAS_EXTENSION)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GuidEntry {
+    /// Asset path relative to the project root, always with `/` separators
+    pub path: String,
+    /// Importer section of the `.meta` file (TextureImporter, NativeFormatImporter...)
+    pub importer: String,
+    /// Main object and sub-asset file IDs declared by the `.meta` file
+    pub file_ids: Vec<i64>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GuidIndex {
+    root: PathBuf,
+    entries: HashMap<String, GuidEntry>,
+    path_to_guid: HashMap<String, String>,
+    /// `.spriteatlas` paths, so sprite lookups do not scan every entry
+    sprite_atlases: BTreeSet<String>,
+}
+
+/// Cheap fingerprint of the `.meta` files, to notice changes made while nothing was watching
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct MetaSignature {
+    count: usize,
+    /// Newest `.meta` modification time, seconds since the epoch
+    latest_modified: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedIndex<'a> {
+    version: u32,
+    signature: MetaSignature,
+    entries: &'a HashMap<String, GuidEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoadedIndex {
+    version: u32,
+    signature: MetaSignature,
+    entries: HashMap<String, GuidEntry>,
+}
+
+/// Project path -> GUID index, shared between commands and the file watcher
+pub type GuidIndexState = Arc<Mutex<HashMap<String, Arc<GuidIndex>>>>;
+
+pub fn create_guid_index_state() -> GuidIndexState {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+fn project_key(project_path: &str) -> String {
+    project_path.trim_end_matches(['/', '\\']).to_string()
+}
+
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(root).ok()?;
+    Some(rel.to_string_lossy().replace('\\', "/"))
+}
+
+fn meta_files(root: &Path) -> Result<Vec<PathBuf>, String> {
+    let assets_dir = root.join("Assets");
+    if !assets_dir.is_dir() {
+        return Err(format!(
+            "Assets folder not found in project: {}",
+            root.display()
+        ));
+    }
+    let pattern = format!("{}/**/*.meta", assets_dir.to_string_lossy());
+    let meta_files = glob(&pattern).map_err(|e| format!("Invalid glob pattern: {}", e))?;
+    Ok(meta_files.filter_map(Result::ok).collect())
+}
+
+// Chỉ stat file, không đọc nội dung
+fn meta_signature(meta_files: &[PathBuf]) -> MetaSignature {
+    let latest_modified = meta_files
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .filter_map(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .max()
+        .unwrap_or(0);
+    MetaSignature {
+        count: meta_files.len(),
+        latest_modified,
+    }
+}
+
+impl GuidIndex {
+    /// Scan every `.meta` file under `<project>/Assets` once
+    pub fn build(project_path: &str) -> Result<GuidIndex, String> {
+        let root = PathBuf::from(project_key(project_path));
+        let meta_files = meta_files(&root)?;
+
+        let mut index = GuidIndex {
+            root,
+            ..Default::default()
+        };
+        for meta_file in &meta_files {
+            index.update_meta(meta_file);
+        }
+
+        println!(
+            "[GuidIndex] Indexed {} assets in {}",
+            index.entries.len(),
+            index.root.display()
+        );
+        Ok(index)
+    }
+
+    fn index_file(root: &Path) -> PathBuf {
+        root.join(INDEX_DIR).join(INDEX_FILE)
+    }
+
+    /// Load a previously saved index, `None` if missing, from another version, or stale
+    /// (`.meta` files added, removed or modified since it was saved)
+    pub fn load(project_path: &str) -> Option<GuidIndex> {
+        let root = PathBuf::from(project_key(project_path));
+        let content = fs::read_to_string(Self::index_file(&root)).ok()?;
+        let loaded: LoadedIndex = serde_json::from_str(&content).ok()?;
+        if loaded.version != INDEX_VERSION {
+            return None;
+        }
+        if loaded.signature != meta_signature(&meta_files(&root).ok()?) {
+            println!(
+                "[GuidIndex] Saved index is stale, rebuilding {}",
+                root.display()
+            );
+            return None;
+        }
+        let path_to_guid = loaded
+            .entries
+            .iter()
+            .map(|(guid, entry)| (entry.path.clone(), guid.clone()))
+            .collect();
+        let sprite_atlases = loaded
+            .entries
+            .values()
+            .filter(|entry| is_sprite_atlas(&entry.path))
+            .map(|entry| entry.path.clone())
+            .collect();
+        Some(GuidIndex {
+            root,
+            entries: loaded.entries,
+            path_to_guid,
+            sprite_atlases,
+        })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let index_file = Self::index_file(&self.root);
+        if let Some(parent) = index_file.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create index directory: {}", e))?;
+        }
+        let saved = SavedIndex {
+            version: INDEX_VERSION,
+            signature: meta_signature(&meta_files(&self.root)?),
+            entries: &self.entries,
+        };
+        let json = serde_json::to_string(&saved)
+            .map_err(|e| format!("Failed to serialize GUID index: {}", e))?;
+        // Ghi file tạm rồi rename để không bao giờ để lại index ghi dở
+        let temp_file = index_file.with_extension("json.tmp");
+        fs::write(&temp_file, json).map_err(|e| format!("Failed to write GUID index: {}", e))?;
+        fs::rename(&temp_file, &index_file)
+            .map_err(|e| format!("Failed to write GUID index: {}", e))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn asset_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn entry(&self, guid: &str) -> Option<&GuidEntry> {
+        self.entries.get(guid)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &GuidEntry)> {
+        self.entries.iter()
+    }
+
+    /// Absolute path of the asset with this GUID, if it still exists on disk
+    pub fn resolve(&self, guid: &str) -> Option<PathBuf> {
+        let entry = self.entries.get(guid)?;
+        let path = self.root.join(&entry.path);
+        if path.exists() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    /// Same as `resolve` but as a string, like the old `.meta` scan returned
+    pub fn resolve_str(&self, guid: &str) -> Option<String> {
+        self.resolve(guid).map(|p| p.to_string_lossy().to_string())
+    }
+
+    /// Resolve a command argument given either as a file path or as a GUID
+    pub fn resolve_path_or_guid(
+        &self,
+        path: Option<&str>,
+        guid: Option<&str>,
+    ) -> Result<PathBuf, String> {
+        if let Some(path) = path.filter(|p| !p.is_empty()) {
+            let path = PathBuf::from(path);
+            if !path.exists() {
+                return Err(format!("File not found: {}", path.display()));
+            }
+            return Ok(path);
+        }
+        let guid = guid
+            .filter(|g| !g.is_empty())
+            .ok_or("Either a path or a GUID is required")?;
+        self.resolve(guid)
+            .ok_or_else(|| format!("GUID not found in project: {}", guid))
+    }
+
+    /// Absolute paths of every `.spriteatlas` in the project
+    pub fn sprite_atlases(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.sprite_atlases.iter().map(|path| self.root.join(path))
+    }
+
+    pub fn guid_for_path(&self, asset_path: &Path) -> Option<&String> {
+        let rel = relative_path(&self.root, asset_path)?;
+        self.path_to_guid.get(&rel)
+    }
+
+    /// Re-read one `.meta` file (created or modified)
+    pub fn update_meta(&mut self, meta_path: &Path) -> bool {
+        let asset_path = meta_path.with_extension("");
+        if asset_path.is_dir() {
+            return false;
+        }
+        let Some(rel) = relative_path(&self.root, &asset_path) else {
+            return false;
+        };
+        let Ok(content) = fs::read_to_string(meta_path) else {
+            return false;
+        };
+        let Some((guid, importer, file_ids)) = parse_meta(&content) else {
+            return false;
+        };
+
+        // Một asset có thể đổi GUID, xóa entry cũ trước
+        if let Some(old_guid) = self.path_to_guid.get(&rel) {
+            if *old_guid != guid {
+                self.entries.remove(old_guid);
+            }
+        }
+        if let Some(old_entry) = self.entries.get(&guid) {
+            if old_entry.path != rel {
+                self.path_to_guid.remove(&old_entry.path);
+                self.sprite_atlases.remove(&old_entry.path);
+            }
+        }
+
+        if is_sprite_atlas(&rel) {
+            self.sprite_atlases.insert(rel.clone());
+        }
+        self.path_to_guid.insert(rel.clone(), guid.clone());
+        self.entries.insert(
+            guid,
+            GuidEntry {
+                path: rel,
+                importer,
+                file_ids,
+            },
+        );
+        true
+    }
+
+    /// Drop the entry of a deleted `.meta` file
+    pub fn remove_meta(&mut self, meta_path: &Path) -> bool {
+        let asset_path = meta_path.with_extension("");
+        let Some(rel) = relative_path(&self.root, &asset_path) else {
+            return false;
+        };
+        self.sprite_atlases.remove(&rel);
+        match self.path_to_guid.remove(&rel) {
+            Some(guid) => {
+                self.entries.remove(&guid);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Đọc guid, importer và file IDs từ nội dung .meta mà không cần parse YAML đầy đủ
+pub(crate) fn parse_meta(content: &str) -> Option<(String, String, Vec<i64>)> {
+    let mut guid = None;
+    let mut importer = String::new();
+    let mut file_ids: Vec<i64> = Vec::new();
+    let mut section = "";
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if indent == 0 {
+            if let Some(value) = trimmed.strip_prefix("guid:") {
+                guid = Some(value.trim().to_string());
+            } else if importer.is_empty() && trimmed.ends_with("Importer:") {
+                importer = trimmed.trim_end_matches(':').to_string();
+            }
+            continue;
+        }
+
+        if indent == 2 && trimmed.ends_with(':') && !trimmed.starts_with('-') {
+            section = trimmed.trim_end_matches(':');
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim_start_matches("- ").trim();
+        let value = value.trim();
+
+        let id = match key {
+            "mainObjectFileID" | "internalID" => value.parse::<i64>().ok(),
+            // fileIDToRecycleName: "21300000: Sprite_0"
+            _ if section == "fileIDToRecycleName" => key.parse::<i64>().ok(),
+            // internalIDToNameTable: "- first: {213: 2130000}" hoặc dạng block
+            "first" if section == "internalIDToNameTable" => value
+                .strip_prefix('{')
+                .and_then(|v| v.strip_suffix('}'))
+                .and_then(|v| v.split_once(':'))
+                .and_then(|(_, id)| id.trim().parse::<i64>().ok()),
+            _ if section == "internalIDToNameTable" && key.parse::<i64>().is_ok() => {
+                value.parse::<i64>().ok()
+            }
+            _ => None,
+        };
+        if let Some(id) = id {
+            if id != 0 && !file_ids.contains(&id) {
+                file_ids.push(id);
+            }
+        }
+    }
+
+    Some((guid?, importer, file_ids))
+}
+
+fn loaded_index(state: &GuidIndexState, key: &str) -> Result<Option<Arc<GuidIndex>>, String> {
+    let indexes = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    Ok(indexes.get(key).cloned())
+}
+
+// Mỗi project một khóa build: lệnh đến sau chờ lần build đang chạy rồi dùng lại kết quả
+fn build_lock(key: &str) -> Result<Arc<Mutex<()>>, String> {
+    static LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?;
+    Ok(locks.entry(key.to_string()).or_default().clone())
+}
+
+fn load_or_build(state: &GuidIndexState, key: &str) -> Result<Arc<GuidIndex>, String> {
+    let lock = build_lock(key)?;
+    let _building = lock.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if let Some(index) = loaded_index(state, key)? {
+        return Ok(index);
+    }
+
+    // Load/build không giữ lock của state, các project khác và watcher vẫn chạy
+    let index = match GuidIndex::load(key) {
+        Some(index) => index,
+        None => {
+            let index = GuidIndex::build(key)?;
+            if let Err(e) = index.save() {
+                println!("[GuidIndex] {}", e);
+            }
+            index
+        }
+    };
+    let index = Arc::new(index);
+    state
+        .lock()
+        .map_err(|e| format!("Lock error: {}", e))?
+        .insert(key.to_string(), index.clone());
+    Ok(index)
+}
+
+/// Get the index of a project: memory first, then disk, otherwise build and save it
+pub async fn project_index(
+    state: &GuidIndexState,
+    project_path: &str,
+) -> Result<Arc<GuidIndex>, String> {
+    let key = project_key(project_path);
+    if let Some(index) = loaded_index(state, &key)? {
+        return Ok(index);
+    }
+    // Quét .meta là việc chặn, chạy trên blocking pool
+    let state = state.clone();
+    tauri::async_runtime::spawn_blocking(move || load_or_build(&state, &key))
+        .await
+        .map_err(|e| format!("Failed to load GUID index: {}", e))?
+}
+
+fn pending_saves() -> &'static Mutex<HashMap<String, Instant>> {
+    static PENDING: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+    PENDING.get_or_init(Default::default)
+}
+
+// Mỗi project có tối đa một thread chờ; event mới chỉ dời mốc thời gian
+fn schedule_save(state: &GuidIndexState, key: &str) {
+    let Ok(mut pending) = pending_saves().lock() else {
+        return;
+    };
+    if pending.insert(key.to_string(), Instant::now()).is_some() {
+        return;
+    }
+    let state = state.clone();
+    let key = key.to_string();
+    thread::spawn(move || loop {
+        thread::sleep(SAVE_DEBOUNCE);
+        let Ok(mut pending) = pending_saves().lock() else {
+            return;
+        };
+        match pending.get(&key) {
+            Some(last) if last.elapsed() < SAVE_DEBOUNCE => continue,
+            Some(_) => {
+                pending.remove(&key);
+            }
+            None => return,
+        }
+        drop(pending);
+        // Chỉ giữ lock để lấy Arc, ghi file ở ngoài
+        let index = state.lock().ok().and_then(|i| i.get(&key).cloned());
+        if let Some(index) = index {
+            if let Err(e) = index.save() {
+                println!("[GuidIndex] {}", e);
+            }
+        }
+        return;
+    });
+}
+
+fn pending_metas() -> &'static Mutex<HashMap<String, BTreeSet<PathBuf>>> {
+    static PENDING: OnceLock<Mutex<HashMap<String, BTreeSet<PathBuf>>>> = OnceLock::new();
+    PENDING.get_or_init(Default::default)
+}
+
+/// Keep loaded indexes in sync with `.meta` changes reported by the file watcher
+pub fn apply_fs_event(state: &GuidIndexState, event: &notify::Event) {
+    if matches!(event.kind, notify::EventKind::Access(_)) {
+        return;
+    }
+    let meta_paths: Vec<&PathBuf> = event
+        .paths
+        .iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "meta"))
+        .collect();
+    if meta_paths.is_empty() {
+        return;
+    }
+
+    let roots: Vec<(String, PathBuf)> = match state.lock() {
+        Ok(indexes) => indexes
+            .iter()
+            .map(|(key, index)| (key.clone(), index.root.clone()))
+            .collect(),
+        Err(_) => return,
+    };
+    let Ok(mut pending) = pending_metas().lock() else {
+        return;
+    };
+    for (key, root) in roots {
+        let paths = meta_paths
+            .iter()
+            .filter(|p| p.starts_with(&root))
+            .map(|p| (*p).clone());
+        let queued = pending.entry(key.clone()).or_default();
+        let was_empty = queued.is_empty();
+        queued.extend(paths);
+        if queued.is_empty() {
+            pending.remove(&key);
+        } else if was_empty {
+            spawn_apply(state, key);
+        }
+    }
+}
+
+// Gom các .meta đổi trong APPLY_DELAY, sửa một bản sao ngoài lock rồi thay vào state
+fn spawn_apply(state: &GuidIndexState, key: String) {
+    let state = state.clone();
+    thread::spawn(move || {
+        thread::sleep(APPLY_DELAY);
+        let Some(meta_paths) = pending_metas()
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.remove(&key))
+        else {
+            return;
+        };
+        loop {
+            let Some(current) = state.lock().ok().and_then(|i| i.get(&key).cloned()) else {
+                return;
+            };
+            let mut index = GuidIndex::clone(&current);
+            let mut changed = false;
+            for meta_path in &meta_paths {
+                changed |= if meta_path.exists() {
+                    index.update_meta(meta_path)
+                } else {
+                    index.remove_meta(meta_path)
+                };
+            }
+            if !changed {
+                return;
+            }
+            let Ok(mut indexes) = state.lock() else {
+                return;
+            };
+            match indexes.get_mut(&key) {
+                // Index vừa được build lại: áp dụng lại lên bản mới
+                Some(latest) if !Arc::ptr_eq(latest, &current) => continue,
+                Some(latest) => *latest = Arc::new(index),
+                None => return,
+            }
+            drop(indexes);
+            schedule_save(&state, &key);
+            return;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn rebuild_guid_index(
+    project_path: String,
+    state: State<'_, GuidIndexState>,
+) -> Result<usize, String> {
+    let key = project_key(&project_path);
+    let state = state.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let lock = build_lock(&key)?;
+        let _building = lock.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let index = GuidIndex::build(&key)?;
+        index.save()?;
+        let count = index.asset_count();
+        let mut indexes = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        indexes.insert(key, Arc::new(index));
+        Ok(count)
+    })
+    .await
+    .map_err(|e| format!("Failed to rebuild GUID index: {}", e))?
+}
+
+#[tauri::command]
+pub async fn resolve_guid(
+    project_path: String,
+    guid: String,
+    state: State<'_, GuidIndexState>,
+) -> Result<Option<GuidEntry>, String> {
+    let index = project_index(&state, &project_path).await?;
+    Ok(index.entry(&guid).cloned())
+}
@@ -0,0 +1,500 @@
+use crate::unity::document::{parse_unity_documents, UnityDocument, CLASS_MONO_BEHAVIOUR};
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+pub type LocalizationState = Arc<Mutex<HashMap<String, Arc<LocalizationTable>>>>;
+
+pub fn create_localization_state() -> LocalizationState {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+// Tên ngôn ngữ (I2, header CSV) -> mã ngôn ngữ
+const LANGUAGES: &[(&str, &str)] = &[
+    ("english", "en"),
+    ("vietnamese", "vi"),
+    ("japanese", "ja"),
+    ("korean", "ko"),
+    ("chinese", "zh"),
+    ("chinese (simplified)", "zh-Hans"),
+    ("chinese (traditional)", "zh-Hant"),
+    ("thai", "th"),
+    ("indonesian", "id"),
+    ("french", "fr"),
+    ("german", "de"),
+    ("spanish", "es"),
+    ("portuguese", "pt"),
+    ("russian", "ru"),
+    ("italian", "it"),
+    ("turkish", "tr"),
+    ("arabic", "ar"),
+];
+
+// Cột CSV không phải ngôn ngữ
+const KEY_HEADERS: &[&str] = &["key", "keys", "id", "term", "name"];
+const IGNORED_HEADERS: &[&str] = &["type", "desc", "description", "comment", "context", "note"];
+
+/// Every localized string found in the project: key -> language -> text
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizationTable {
+    pub languages: Vec<String>,
+    pub entries: BTreeMap<String, BTreeMap<String, String>>,
+    /// Project-relative paths the entries were read from
+    pub sources: Vec<String>,
+}
+
+impl LocalizationTable {
+    fn insert(&mut self, key: &str, language: &str, text: &str) -> bool {
+        if key.is_empty() || text.is_empty() {
+            return false;
+        }
+        if !self.languages.iter().any(|l| l == language) {
+            self.languages.push(language.to_string());
+        }
+        self.entries
+            .entry(key.to_string())
+            .or_default()
+            .insert(language.to_string(), text.to_string());
+        true
+    }
+
+    /// Entries of a key; `Hero/Knight/*` returns every key with that prefix
+    pub fn lookup(&self, key: &str) -> BTreeMap<String, BTreeMap<String, String>> {
+        match key.strip_suffix('*') {
+            Some(prefix) => self
+                .entries
+                .range(prefix.to_string()..)
+                .take_while(|(k, _)| k.starts_with(prefix))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            None => self
+                .entries
+                .get_key_value(key)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// Mã ngôn ngữ của một tên ("English", "en", "zh-Hans"...), `None` nếu không nhận ra
+fn language_code(name: &str) -> Option<String> {
+    let name = name.trim();
+    let lower = name.to_lowercase();
+    if let Some((_, code)) = LANGUAGES.iter().find(|(n, _)| *n == lower) {
+        return Some(code.to_string());
+    }
+    if let Some((_, code)) = LANGUAGES.iter().find(|(_, c)| c.to_lowercase() == lower) {
+        return Some(code.to_string());
+    }
+    // "English (en)", "en-US"
+    if let Some(code) = lower
+        .split_once('(')
+        .and_then(|(_, rest)| rest.strip_suffix(')'))
+    {
+        return language_code(code);
+    }
+    let (base, region) = lower.split_once(['-', '_']).unwrap_or((&lower, ""));
+    if LANGUAGES.iter().any(|(_, c)| *c == base) && region.len() <= 4 {
+        return Some(name.replace('_', "-"));
+    }
+    None
+}
+
+fn column_language(header: &str) -> String {
+    language_code(header).unwrap_or_else(|| header.trim().to_string())
+}
+
+// CSV có hỗ trợ ô trong ngoặc kép nhiều dòng (I2 export)
+fn parse_csv(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.is_empty() => quoted = true,
+            c if c == delimiter && !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    rows
+}
+
+fn read_csv(table: &mut LocalizationTable, text: &str, delimiter: char) -> bool {
+    let rows = parse_csv(text, delimiter);
+    let Some(header) = rows.first() else {
+        return false;
+    };
+    let is_key = |h: &String| KEY_HEADERS.contains(&h.trim().to_lowercase().as_str());
+    let Some(key_column) = header.iter().position(is_key) else {
+        return false;
+    };
+    let columns: Vec<(usize, String)> = header
+        .iter()
+        .enumerate()
+        .filter(|(i, h)| {
+            *i != key_column
+                && !h.trim().is_empty()
+                && !IGNORED_HEADERS.contains(&h.trim().to_lowercase().as_str())
+        })
+        .map(|(i, h)| (i, column_language(h)))
+        .collect();
+    if columns.is_empty() {
+        return false;
+    }
+    for row in &rows[1..] {
+        let Some(key) = row.get(key_column) else {
+            continue;
+        };
+        for (i, language) in &columns {
+            if let Some(text) = row.get(*i) {
+                table.insert(key.trim(), language, text);
+            }
+        }
+    }
+    true
+}
+
+// i18next: {"hero": {"name": "..."}} -> key "hero.name"
+fn flatten_strings(prefix: &str, value: &Value, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::String(s) => out.push((prefix.to_string(), s.clone())),
+        Value::Object(map) => {
+            for (k, v) in map {
+                let key = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten_strings(&key, v, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn read_json(table: &mut LocalizationTable, text: &str, file_stem: &str) -> bool {
+    let Ok(Value::Object(root)) =
+        serde_json::from_str::<Value>(text.trim_start_matches('\u{feff}'))
+    else {
+        return false;
+    };
+    let mut found = false;
+    // en.json: {key: text}
+    if let Some(language) = language_code(file_stem) {
+        let mut strings = Vec::new();
+        flatten_strings("", &Value::Object(root), &mut strings);
+        for (key, text) in strings {
+            found |= table.insert(&key, &language, &text);
+        }
+        return found;
+    }
+    if !root.values().all(Value::is_object) {
+        return false;
+    }
+    let language_first = root.keys().all(|k| language_code(k).is_some());
+    for (outer, inner) in &root {
+        let mut strings = Vec::new();
+        flatten_strings("", inner, &mut strings);
+        for (inner_key, text) in strings {
+            if language_first {
+                found |= table.insert(&inner_key, &column_language(outer), &text);
+            } else if let Some(language) = language_code(&inner_key) {
+                found |= table.insert(outer, &language, &text);
+            }
+        }
+    }
+    found
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key)?.as_str()
+}
+
+// I2 Localization: LanguageSource (mSource.mTerms / mLanguages, bản cũ để ở gốc)
+fn read_i2_source(table: &mut LocalizationTable, document: &UnityDocument) -> bool {
+    let source = document.body.get("mSource").unwrap_or(&document.body);
+    let (Some(Value::Array(terms)), Some(Value::Array(languages))) =
+        (source.get("mTerms"), source.get("mLanguages"))
+    else {
+        return false;
+    };
+    let languages: Vec<String> = languages
+        .iter()
+        .map(|l| {
+            str_field(l, "Code")
+                .filter(|c| !c.is_empty())
+                .map(str::to_string)
+                .unwrap_or_else(|| column_language(str_field(l, "Name").unwrap_or_default()))
+        })
+        .collect();
+    for term in terms {
+        let Some(key) = str_field(term, "Term") else {
+            continue;
+        };
+        let Some(Value::Array(texts)) = term.get("Languages") else {
+            continue;
+        };
+        for (language, text) in languages.iter().zip(texts) {
+            if let Some(text) = text.as_str() {
+                table.insert(key, language, text);
+            }
+        }
+    }
+    true
+}
+
+// Unity Localization: StringTable (m_TableData) + SharedTableData (m_TableEntries)
+struct StringTable {
+    language: String,
+    shared_guid: Option<String>,
+    entries: Vec<(i64, String)>,
+}
+
+struct SharedTable {
+    collection: String,
+    keys: HashMap<i64, String>,
+}
+
+fn string_table(document: &UnityDocument) -> Option<StringTable> {
+    let Value::Array(data) = document.body.get("m_TableData")? else {
+        return None;
+    };
+    let language = document.body.get("m_LocaleId")?.get("m_Code")?.as_str()?;
+    Some(StringTable {
+        language: column_language(language),
+        shared_guid: document
+            .body
+            .get("m_SharedData")
+            .and_then(|s| str_field(s, "guid"))
+            .map(str::to_string),
+        entries: data
+            .iter()
+            .filter_map(|e| {
+                Some((
+                    e.get("m_Id")?.as_i64()?,
+                    str_field(e, "m_Localized")?.to_string(),
+                ))
+            })
+            .collect(),
+    })
+}
+
+fn shared_table(document: &UnityDocument) -> Option<SharedTable> {
+    let Value::Array(entries) = document.body.get("m_TableEntries")? else {
+        return None;
+    };
+    Some(SharedTable {
+        collection: str_field(&document.body, "m_TableCollectionName")
+            .unwrap_or_default()
+            .to_string(),
+        keys: entries
+            .iter()
+            .filter_map(|e| Some((e.get("m_Id")?.as_i64()?, str_field(e, "m_Key")?.to_string())))
+            .collect(),
+    })
+}
+
+// File text có khả năng là bảng ngôn ngữ
+fn is_text_source(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let text_file = [".csv", ".tsv", ".json", ".txt"]
+        .iter()
+        .any(|ext| lower.ends_with(ext));
+    text_file
+        && ["locali", "i18n", "language", "/lang/", "/locales/"]
+            .iter()
+            .any(|hint| lower.contains(hint))
+}
+
+/// Collect localization from I2 LanguageSources, Unity string tables and text assets
+pub fn build_localization_table(index: &GuidIndex) -> LocalizationTable {
+    let mut table = LocalizationTable::default();
+    let mut paths: Vec<(&String, &String)> = index
+        .entries()
+        .map(|(guid, entry)| (&entry.path, guid))
+        .collect();
+    paths.sort();
+
+    let mut string_tables: Vec<(String, StringTable)> = Vec::new();
+    let mut shared_tables: HashMap<String, SharedTable> = HashMap::new();
+    for (path, guid) in paths {
+        let full_path = index.root().join(path);
+        let lower = path.to_lowercase();
+        let found = if lower.ends_with(".asset") || lower.ends_with(".prefab") {
+            let Ok(content) = fs::read_to_string(&full_path) else {
+                continue;
+            };
+            if !["mTerms", "m_TableData", "m_TableEntries"]
+                .iter()
+                .any(|marker| content.contains(marker))
+            {
+                continue;
+            }
+            let mut found = false;
+            for document in parse_unity_documents(&content)
+                .iter()
+                .filter(|d| d.class_id == CLASS_MONO_BEHAVIOUR && d.error.is_none())
+            {
+                if read_i2_source(&mut table, document) {
+                    found = true;
+                } else if let Some(strings) = string_table(document) {
+                    string_tables.push((path.clone(), strings));
+                } else if let Some(shared) = shared_table(document) {
+                    shared_tables.insert(guid.clone(), shared);
+                }
+            }
+            found
+        } else if is_text_source(path) {
+            let Ok(text) = fs::read_to_string(&full_path) else {
+                continue;
+            };
+            let stem = Path::new(path)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if lower.ends_with(".json") || text.trim_start().starts_with('{') {
+                read_json(&mut table, &text, &stem)
+            } else if lower.ends_with(".tsv") {
+                read_csv(&mut table, &text, '\t')
+            } else {
+                read_csv(&mut table, &text, ',')
+            }
+        } else {
+            false
+        };
+        if found {
+            table.sources.push(path.clone());
+        }
+    }
+
+    for (path, strings) in string_tables {
+        let Some(shared) = strings
+            .shared_guid
+            .as_ref()
+            .and_then(|g| shared_tables.get(g))
+        else {
+            continue;
+        };
+        for (id, text) in &strings.entries {
+            let Some(key) = shared.keys.get(id) else {
+                continue;
+            };
+            let key = if shared.collection.is_empty() {
+                key.clone()
+            } else {
+                format!("{}/{}", shared.collection, key)
+            };
+            table.insert(&key, &strings.language, text);
+        }
+        if !table.sources.contains(&path) {
+            table.sources.push(path);
+        }
+    }
+    table
+}
+
+fn project_localization(
+    state: &LocalizationState,
+    index: &GuidIndex,
+    reload: bool,
+) -> Result<Arc<LocalizationTable>, String> {
+    let key = index.root().to_string_lossy().to_string();
+    if !reload {
+        let tables = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        if let Some(table) = tables.get(&key) {
+            return Ok(table.clone());
+        }
+    }
+    // Đọc cả project mất thời gian, không giữ lock trong lúc build
+    let table = Arc::new(build_localization_table(index));
+    let mut tables = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if reload {
+        tables.insert(key, table.clone());
+        return Ok(table);
+    }
+    Ok(tables.entry(key).or_insert(table).clone())
+}
+
+// File mà build_localization_table có thể đọc: asset (I2, string table) hoặc bảng text
+fn is_localization_source(path: &Path) -> bool {
+    let path = path.to_string_lossy().replace('\\', "/");
+    path.to_lowercase().ends_with(".asset") || is_text_source(&path)
+}
+
+/// Drop the cached table of every project containing a changed localization source
+pub fn invalidate_localization(state: &LocalizationState, event: &notify::Event) {
+    if matches!(event.kind, notify::EventKind::Access(_)) {
+        return;
+    }
+    let Ok(mut tables) = state.lock() else {
+        return;
+    };
+    // Chỉ asset trong Assets/ có trong GUID index, Library/ đổi liên tục khi Unity import
+    tables.retain(|root, _| {
+        let assets_dir = Path::new(root).join("Assets");
+        !event
+            .paths
+            .iter()
+            .any(|p| p.starts_with(&assets_dir) && is_localization_source(p))
+    });
+}
+
+#[tauri::command]
+pub async fn localization_table(
+    project_path: String,
+    reload: Option<bool>,
+    guid_index: State<'_, GuidIndexState>,
+    localization: State<'_, LocalizationState>,
+) -> Result<LocalizationTable, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let table = project_localization(&localization, &index, reload.unwrap_or(false))?;
+    Ok((*table).clone())
+}
+
+/// Localized texts of the given keys (hero name, skill descriptions...);
+/// a key ending with `*` matches every key with that prefix
+#[tauri::command]
+pub async fn localize_keys(
+    project_path: String,
+    keys: Vec<String>,
+    language: Option<String>,
+    guid_index: State<'_, GuidIndexState>,
+    localization: State<'_, LocalizationState>,
+) -> Result<BTreeMap<String, BTreeMap<String, String>>, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let table = project_localization(&localization, &index, false)?;
+    let language = language.map(|l| column_language(&l));
+    let mut result = BTreeMap::new();
+    for key in &keys {
+        for (key, mut texts) in table.lookup(key) {
+            if let Some(language) = &language {
+                texts.retain(|l, _| l == language);
+            }
+            result.insert(key, texts);
+        }
+    }
+    Ok(result)
+}
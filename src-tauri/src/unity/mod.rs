@@ -1 +1,22 @@
+pub mod animation;
+pub mod animator;
+pub mod curve;
+pub mod data_table;
+pub mod dependency;
+pub mod document;
+pub mod error;
+pub mod guid_index;
+pub mod localization;
+pub mod mono_script;
+pub mod package;
 pub mod prefab;
+pub mod prefab_diff;
+pub mod prefab_instance;
+pub mod reference;
+pub mod render;
+pub mod snapshot;
+pub mod sprite_atlas;
+pub mod sprite_mesh;
+pub mod texture;
+pub mod transform;
+pub mod yaml_writer;
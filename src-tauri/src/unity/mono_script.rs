@@ -0,0 +1,190 @@
+use crate::unity::document::parse_unity_documents;
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use tauri::State;
+
+const CLASS_MONO_SCRIPT: i64 = 115;
+const DEFAULT_ASSEMBLY: &str = "Assembly-CSharp";
+const FIRSTPASS_ASSEMBLY: &str = "Assembly-CSharp-firstpass";
+
+/// C# class behind a MonoBehaviour's `m_Script`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptInfo {
+    pub guid: String,
+    pub class_name: Option<String>,
+    pub namespace: Option<String>,
+    pub assembly: Option<String>,
+    /// Project-relative path of the `.cs`, MonoScript `.asset` or `.dll`
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MonoScriptDoc {
+    #[serde(rename = "m_ClassName", default)]
+    m_class_name: String,
+    #[serde(rename = "m_Namespace", default)]
+    m_namespace: String,
+    #[serde(rename = "m_AssemblyName", default)]
+    m_assembly_name: String,
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn comment_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?s)/\*.*?\*/|//[^\n]*").unwrap())
+}
+
+fn namespace_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\bnamespace\s+([A-Za-z_][\w.]*)").unwrap())
+}
+
+fn class_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\bclass\s+([A-Za-z_]\w*)").unwrap())
+}
+
+// Unity yêu cầu tên class MonoBehaviour trùng tên file, nếu không có thì lấy class đầu tiên
+fn parse_cs_script(source: &str, file_stem: &str) -> (Option<String>, Option<String>) {
+    let code = comment_regex().replace_all(source, "");
+    let namespace = namespace_regex().captures(&code).map(|c| c[1].to_string());
+    let classes: Vec<&str> = class_regex()
+        .captures_iter(&code)
+        .filter_map(|c| c.get(1).map(|m| m.as_str()))
+        .collect();
+    let class_name = classes
+        .iter()
+        .find(|c| **c == file_stem)
+        .or(classes.first())
+        .map(|c| c.to_string());
+    (namespace, class_name)
+}
+
+// Assembly theo .asmdef gần nhất, mặc định là Assembly-CSharp(-firstpass)
+fn script_assembly(script_path: &Path, root: &Path) -> String {
+    let mut dir = script_path.parent();
+    while let Some(current) = dir {
+        if !current.starts_with(root) {
+            break;
+        }
+        let asmdef = fs::read_dir(current).ok().and_then(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .find(|p| p.extension().is_some_and(|ext| ext == "asmdef"))
+        });
+        if let Some(asmdef) = asmdef {
+            let name = fs::read_to_string(&asmdef)
+                .ok()
+                .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+                .and_then(|v| v.get("name")?.as_str().map(str::to_string));
+            if let Some(name) = name {
+                return name;
+            }
+        }
+        dir = current.parent();
+    }
+
+    let relative = script_path.strip_prefix(root).unwrap_or(script_path);
+    let firstpass = relative.components().any(|c| {
+        matches!(
+            c.as_os_str().to_str(),
+            Some("Plugins" | "Standard Assets" | "Pro Standard Assets")
+        )
+    });
+    if firstpass {
+        FIRSTPASS_ASSEMBLY.to_string()
+    } else {
+        DEFAULT_ASSEMBLY.to_string()
+    }
+}
+
+/// Resolve a script GUID to its class name, namespace and assembly
+pub fn resolve_script(guid: &str, index: &GuidIndex) -> Option<ScriptInfo> {
+    let path = index.resolve(guid)?;
+    let mut info = ScriptInfo {
+        guid: guid.to_string(),
+        class_name: None,
+        namespace: None,
+        assembly: None,
+        path: index.entry(guid).map(|e| e.path.clone()),
+    };
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "cs" => {
+            let source = fs::read_to_string(&path).ok()?;
+            let stem = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let (namespace, class_name) = parse_cs_script(&source, &stem);
+            info.namespace = namespace;
+            info.class_name = class_name;
+            info.assembly = Some(script_assembly(&path, index.root()));
+        }
+        // AssetRipper xuất MonoScript của DLL thành .asset có m_ClassName/m_Namespace
+        "asset" => {
+            let content = fs::read_to_string(&path).ok()?;
+            let script: MonoScriptDoc = parse_unity_documents(&content)
+                .into_iter()
+                .find(|d| d.class_id == CLASS_MONO_SCRIPT)?
+                .view()
+                .ok()?;
+            info.class_name = non_empty(script.m_class_name);
+            info.namespace = non_empty(script.m_namespace);
+            info.assembly =
+                non_empty(script.m_assembly_name).map(|a| a.trim_end_matches(".dll").to_string());
+        }
+        // Script nằm trong DLL: chỉ biết được assembly
+        "dll" => {
+            info.assembly = path.file_stem().map(|s| s.to_string_lossy().to_string());
+        }
+        _ => return None,
+    }
+    Some(info)
+}
+
+/// Resolve every distinct GUID once
+pub fn resolve_scripts<'a>(
+    guids: impl IntoIterator<Item = &'a str>,
+    index: &GuidIndex,
+) -> HashMap<String, ScriptInfo> {
+    let mut scripts = HashMap::new();
+    for guid in guids {
+        if guid.is_empty() || scripts.contains_key(guid) {
+            continue;
+        }
+        if let Some(info) = resolve_script(guid, index) {
+            scripts.insert(guid.to_string(), info);
+        }
+    }
+    scripts
+}
+
+#[tauri::command]
+pub async fn mono_script_info(
+    project_path: String,
+    script_guid: String,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<Option<ScriptInfo>, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    Ok(resolve_script(&script_guid, &index))
+}
@@ -0,0 +1,284 @@
+use crate::unity::dependency::{project_graph, target_guid, DependencyGraph, DependencyState};
+use crate::unity::guid_index::{parse_meta, project_index, GuidIndex, GuidIndexState};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tauri::State;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const ATLAS_EXTENSIONS: &[&str] = &[".spriteatlas", ".spriteatlasv2"];
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageSummary {
+    pub output_path: String,
+    /// Assets copied, the exported asset included
+    pub asset_count: usize,
+    /// Files written, `.meta` files included
+    pub file_count: usize,
+    pub total_size: u64,
+    /// Referenced GUIDs that are not in the project (built-in or missing assets)
+    pub missing: Vec<String>,
+}
+
+// Path tương đối của asset -> các file cần copy (asset, .meta, .meta của thư mục cha)
+fn package_files(index: &GuidIndex, asset_paths: &BTreeSet<String>) -> BTreeSet<String> {
+    let mut files = BTreeSet::new();
+    for asset_path in asset_paths {
+        let full_path = index.root().join(asset_path);
+        if full_path.is_file() {
+            files.insert(asset_path.clone());
+        }
+        files.insert(format!("{}.meta", asset_path));
+        // Giữ GUID của thư mục để SpriteAtlas/Addressables trỏ theo folder vẫn đúng
+        let mut parent = Path::new(asset_path).parent();
+        while let Some(dir) = parent.filter(|d| d.components().count() > 1) {
+            let meta = format!("{}.meta", dir.to_string_lossy().replace('\\', "/"));
+            if index.root().join(&meta).is_file() {
+                files.insert(meta);
+            }
+            parent = dir.parent();
+        }
+    }
+    files
+}
+
+// GuidIndex bỏ qua thư mục, đọc thẳng `.meta` của folder; cache theo thư mục vì các asset
+// cùng folder hỏi lại cùng một chuỗi thư mục cha
+fn folder_guid(
+    index: &GuidIndex,
+    dir: &Path,
+    cache: &mut HashMap<PathBuf, Option<String>>,
+) -> Option<String> {
+    cache
+        .entry(dir.to_path_buf())
+        .or_insert_with(|| {
+            let meta = index.root().join(format!("{}.meta", dir.to_string_lossy()));
+            let content = fs::read_to_string(meta).ok()?;
+            parse_meta(&content).map(|(guid, _, _)| guid)
+        })
+        .clone()
+}
+
+// Output có thể chưa tồn tại: chuẩn hóa phần cha đã có rồi nối phần còn lại
+fn resolved_path(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return rest.iter().rev().fold(canonical, |p, name| p.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name);
+                existing = if parent.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    parent
+                };
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+// Không ghi vào trong project, không đè file có sẵn khi chưa được phép
+fn check_output(
+    index: &GuidIndex,
+    output: &Path,
+    as_zip: bool,
+    overwrite: bool,
+) -> Result<(), String> {
+    if resolved_path(output).starts_with(resolved_path(index.root())) {
+        return Err(format!(
+            "Output must be outside the project: {}",
+            output.display()
+        ));
+    }
+    if overwrite || !output.exists() {
+        return Ok(());
+    }
+    if as_zip {
+        return Err(format!("Output file already exists: {}", output.display()));
+    }
+    let is_empty = fs::read_dir(output)
+        .map_err(|e| format!("Failed to read output folder: {}", e))?
+        .next()
+        .is_none();
+    if !is_empty {
+        return Err(format!("Output folder is not empty: {}", output.display()));
+    }
+    Ok(())
+}
+
+fn write_folder(index: &GuidIndex, files: &BTreeSet<String>, output: &Path) -> Result<u64, String> {
+    let mut total_size = 0;
+    for file in files {
+        let source = index.root().join(file);
+        let target = output.join(file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory {}: {}", parent.display(), e))?;
+        }
+        total_size += fs::copy(&source, &target)
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+    }
+    Ok(total_size)
+}
+
+fn write_zip(index: &GuidIndex, files: &BTreeSet<String>, output: &Path) -> Result<u64, String> {
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+    let file = File::create(output).map_err(|e| format!("Failed to create zip: {}", e))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut total_size = 0;
+    for file in files {
+        let source = index.root().join(file);
+        let bytes =
+            fs::read(&source).map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        zip.start_file(file.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to zip: {}", file, e))?;
+        zip.write_all(&bytes)
+            .map_err(|e| format!("Failed to write {} to zip: {}", file, e))?;
+        total_size += bytes.len() as u64;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish zip: {}", e))?;
+    Ok(total_size)
+}
+
+/// Package contents: the asset, its dependency closure and the atlases packing any of them
+/// (with their own closure)
+pub fn package_assets(
+    index: &GuidIndex,
+    graph: &DependencyGraph,
+    guid: &str,
+    include_scripts: bool,
+) -> (BTreeSet<String>, Vec<String>) {
+    let mut guids: BTreeSet<String> = BTreeSet::new();
+    let mut missing: BTreeSet<String> = BTreeSet::new();
+    let mut folders: HashMap<PathBuf, Option<String>> = HashMap::new();
+    // Asset mới thêm chưa được kiểm tra xem có nằm trong atlas nào không
+    let mut pending = vec![guid.to_string()];
+    let mut roots = vec![guid.to_string()];
+
+    while let Some(root) = roots.pop() {
+        if !guids.insert(root.clone()) {
+            continue;
+        }
+        for dependency in graph.dependencies(&root, true, index) {
+            match dependency.path {
+                Some(path) if !include_scripts && path.ends_with(".cs") => {}
+                Some(_) => {
+                    if guids.insert(dependency.guid.clone()) {
+                        pending.push(dependency.guid);
+                    }
+                }
+                None => {
+                    missing.insert(dependency.guid);
+                }
+            }
+        }
+
+        // SpriteAtlas trỏ tới sprite (hoặc thư mục chứa sprite) chứ không ngược lại
+        for guid in std::mem::take(&mut pending) {
+            let Some(entry) = index.entry(&guid) else {
+                continue;
+            };
+            let mut candidates = vec![guid.clone()];
+            let mut parent = Path::new(&entry.path).parent();
+            while let Some(dir) = parent {
+                if let Some(dir_guid) = folder_guid(index, dir, &mut folders) {
+                    candidates.push(dir_guid);
+                }
+                parent = dir.parent();
+            }
+            for candidate in candidates {
+                for user in graph.dependents(&candidate, false, index) {
+                    let is_atlas = user
+                        .path
+                        .as_ref()
+                        .is_some_and(|p| ATLAS_EXTENSIONS.iter().any(|ext| p.ends_with(ext)));
+                    if is_atlas && !guids.contains(&user.guid) {
+                        roots.push(user.guid);
+                    }
+                }
+            }
+        }
+    }
+
+    // Atlas trỏ tới folder: GUID của folder không nằm trong index nhưng không phải bị thiếu
+    let folder_guids: BTreeSet<String> = folders.into_values().flatten().collect();
+    missing.retain(|guid| !folder_guids.contains(guid));
+
+    let asset_paths = guids
+        .iter()
+        .filter_map(|g| index.entry(g).map(|e| e.path.clone()))
+        .collect();
+    (asset_paths, missing.into_iter().collect())
+}
+
+/// Copy `guid` and everything it needs into `output` (folder or `.zip`), GUIDs and layout kept
+pub fn export_package(
+    index: &GuidIndex,
+    graph: &DependencyGraph,
+    guid: &str,
+    output: &Path,
+    as_zip: bool,
+    include_scripts: bool,
+    overwrite: bool,
+) -> Result<PackageSummary, String> {
+    check_output(index, output, as_zip, overwrite)?;
+    let (asset_paths, missing) = package_assets(index, graph, guid, include_scripts);
+    let files = package_files(index, &asset_paths);
+    let total_size = if as_zip {
+        write_zip(index, &files, output)?
+    } else {
+        write_folder(index, &files, output)?
+    };
+
+    Ok(PackageSummary {
+        output_path: output.to_string_lossy().to_string(),
+        asset_count: asset_paths.len(),
+        file_count: files.len(),
+        total_size,
+        missing,
+    })
+}
+
+/// Output ending in `.zip` (or `zip = true`) writes an archive instead of a folder.
+/// The output must be outside the project and new or empty, unless `overwrite` is set
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_asset_package(
+    project_path: String,
+    asset_path: Option<String>,
+    asset_guid: Option<String>,
+    output_path: String,
+    zip: Option<bool>,
+    include_scripts: Option<bool>,
+    reload: Option<bool>,
+    overwrite: Option<bool>,
+    guid_index: State<'_, GuidIndexState>,
+    dependency_graph: State<'_, DependencyState>,
+) -> Result<PackageSummary, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let guid = target_guid(&index, asset_path, asset_guid)?;
+    let graph = project_graph(&dependency_graph, &index, reload.unwrap_or(false))?;
+    let as_zip = zip.unwrap_or_else(|| output_path.to_lowercase().ends_with(".zip"));
+    export_package(
+        &index,
+        &graph,
+        &guid,
+        &PathBuf::from(&output_path),
+        as_zip,
+        include_scripts.unwrap_or(true),
+        overwrite.unwrap_or(false),
+    )
+}
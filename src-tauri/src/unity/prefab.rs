@@ -1,13 +1,29 @@
 // Allow camelCase field names to match Unity's naming conventions
 #![allow(non_snake_case)]
 
+use crate::unity::animator::controller_clip_names;
+use crate::unity::document::{
+    class_name, UnityDocument, CLASS_ANIMATOR, CLASS_GAME_OBJECT, CLASS_MONO_BEHAVIOUR,
+    CLASS_PARTICLE_SYSTEM, CLASS_RECT_TRANSFORM, CLASS_SCENE_ROOTS, CLASS_SPRITE_RENDERER,
+    CLASS_TRANSFORM,
+};
+use crate::unity::error::{deserialize_document, DocumentDiagnostic, UnityParseError};
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use crate::unity::mono_script::{resolve_scripts, ScriptInfo};
+use crate::unity::prefab_instance::load_merged_documents;
+use crate::unity::reference::{resolve_references, LocalObject};
+use crate::unity::sprite_atlas::{packed_texture, unpack_rotation};
+use crate::unity::sprite_mesh::{mask_to_mesh, mesh_triangles, needs_mesh_mask};
+use crate::unity::texture::{crop_sheet_sprite, merge_alpha, pivot_offset, TextureImporter};
+use crate::unity::transform::{apply_world_transforms, WorldBounds, IDENTITY};
 use crate::utils::yaml_to_json;
 use base64::{engine::general_purpose, Engine as _};
-use glob::glob;
-use image::ImageReader;
+use image::{ImageReader, RgbaImage};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
+use tauri::State;
 use unity_yaml_rust::yaml::YamlLoader;
 
 #[derive(Serialize, Deserialize)]
@@ -21,59 +37,70 @@ pub struct PrefabElement {
     mono_behaviour: Option<MonoBehaviour>,
     sprite_renderer: Option<SpriteRenderer>,
     particle_system: Option<ParticleSystem>,
+    scene_roots: Option<SceneRoots>,
+    /// Component không có typed view (hoặc typed view lỗi), giữ nguyên cây property
+    #[serde(skip)]
+    raw: Option<UnityDocument>,
+    /// Document không đọc được, giữ lại để báo lỗi
+    #[serde(skip)]
+    diagnostic: Option<DocumentDiagnostic>,
+    #[serde(skip)]
+    type_name: String,
+    /// GameObject sở hữu component (`m_GameObject`)
+    #[serde(skip)]
+    game_object_id: Option<i64>,
+}
+
+impl PrefabElement {
+    fn empty(id: i64) -> Self {
+        PrefabElement {
+            id,
+            game_object: None,
+            transform: None,
+            animator: None,
+            mono_behaviour: None,
+            sprite_renderer: None,
+            particle_system: None,
+            scene_roots: None,
+            raw: None,
+            diagnostic: None,
+            type_name: String::new(),
+            game_object_id: None,
+        }
+    }
 }
 
+/// Thứ tự root của scene (Unity 2022+ không còn m_RootOrder)
+#[derive(Serialize, Deserialize)]
+pub struct SceneRoots {
+    #[serde(rename = "m_Roots", default)]
+    m_roots: Vec<MAvatar>,
+}
+
+// Typed view chỉ giữ các field toolkit thực sự đọc; field Unity thêm/bớt giữa các phiên bản
+// không được làm hỏng cả component
 #[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
 pub struct Animator {
-    #[serde(rename = "m_AllowConstantClipSamplingOptimization")]
-    m_allow_constant_clip_sampling_optimization: i64,
-    #[serde(rename = "m_ApplyRootMotion")]
-    m_apply_root_motion: i64,
-    #[serde(rename = "m_Avatar")]
-    m_avatar: MAvatar,
-    #[serde(rename = "m_Controller")]
+    #[serde(rename = "m_Controller", default)]
     m_controller: MController,
-    #[serde(rename = "m_CorrespondingSourceObject")]
-    m_corresponding_source_object: MAvatar,
-    #[serde(rename = "m_CullingMode")]
-    m_culling_mode: i64,
-    #[serde(rename = "m_Enabled")]
+    #[serde(rename = "m_Enabled", default = "enabled")]
     m_enabled: i64,
     #[serde(rename = "m_GameObject")]
     m_game_object: MAvatar,
-    #[serde(rename = "m_HasTransformHierarchy")]
-    m_has_transform_hierarchy: i64,
-    #[serde(rename = "m_KeepAnimatorStateOnDisable")]
-    m_keep_animator_state_on_disable: i64,
-    #[serde(rename = "m_LinearVelocityBlending")]
-    m_linear_velocity_blending: i64,
-    #[serde(rename = "m_ObjectHideFlags")]
-    m_object_hide_flags: i64,
-    #[serde(rename = "m_PrefabAsset")]
-    m_prefab_asset: MAvatar,
-    #[serde(rename = "m_PrefabInstance")]
-    m_prefab_instance: MAvatar,
-    #[serde(rename = "m_StabilizeFeet")]
-    m_stabilize_feet: i64,
-    #[serde(rename = "m_UpdateMode")]
-    m_update_mode: i64,
-    #[serde(rename = "m_WarningMessage")]
-    m_warning_message: Option<serde_json::Value>,
-    #[serde(rename = "m_WriteDefaultValuesOnDisable")]
-    m_write_default_values_on_disable: i64,
-    #[serde(rename = "serializedVersion", default)]
-    serialized_version: Option<i64>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+fn enabled() -> i64 {
+    1
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct MAvatar {
     #[serde(rename = "fileID")]
     file_id: i64,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct MController {
     #[serde(rename = "fileID")]
@@ -85,85 +112,64 @@ pub struct MController {
 }
 
 #[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
 pub struct GameObject {
-    #[serde(rename = "m_Component")]
-    m_component: Vec<MComponent>,
-    #[serde(rename = "m_CorrespondingSourceObject")]
-    m_corresponding_source_object: MAvatar,
-    #[serde(rename = "m_Icon")]
-    m_icon: MAvatar,
-    #[serde(rename = "m_IsActive")]
+    #[serde(rename = "m_IsActive", default = "enabled")]
     m_is_active: i64,
-    #[serde(rename = "m_Layer")]
-    m_layer: i64,
-    #[serde(rename = "m_Name")]
+    #[serde(rename = "m_Name", default)]
     m_name: String,
-    #[serde(rename = "m_NavMeshLayer")]
-    m_nav_mesh_layer: i64,
-    #[serde(rename = "m_ObjectHideFlags")]
-    m_object_hide_flags: i64,
-    #[serde(rename = "m_PrefabAsset")]
-    m_prefab_asset: MAvatar,
-    #[serde(rename = "m_PrefabInstance")]
-    m_prefab_instance: MAvatar,
-    #[serde(rename = "m_StaticEditorFlags")]
-    m_static_editor_flags: i64,
-    #[serde(rename = "m_TagString")]
-    m_tag_string: String,
-    #[serde(rename = "serializedVersion", default)]
-    serialized_version: Option<i64>,
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct MComponent {
-    component: MAvatar,
+impl GameObject {
+    /// Node vẫn phải có trong cây khi typed view lỗi: lấy tên/active trực tiếp từ body
+    fn fallback(document: &UnityDocument) -> Self {
+        GameObject {
+            m_is_active: document
+                .body
+                .get("m_IsActive")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(1),
+            m_name: document
+                .body
+                .get("m_Name")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
 pub struct MonoBehaviour {
-    actor_image: Option<MAvatar>,
-    animator: Option<MAvatar>,
-    attached_unit_effect_renderers: Option<Vec<Option<serde_json::Value>>>,
-    attached_unit_effects: Option<Vec<Option<serde_json::Value>>>,
-    attack_collider: Option<MAvatar>,
-    effect_root: Option<Vec<Option<serde_json::Value>>>,
-    #[serde(rename = "isUI")]
-    is_ui: Option<i64>,
+    // Field của base class không đọc tới, khai báo để không lẫn vào `fields`
     #[serde(rename = "m_CorrespondingSourceObject")]
-    m_corresponding_source_object: MAvatar,
+    m_corresponding_source_object: Option<serde_json::Value>,
     #[serde(rename = "m_EditorClassIdentifier")]
     m_editor_class_identifier: Option<serde_json::Value>,
     #[serde(rename = "m_EditorHideFlags")]
-    m_editor_hide_flags: i64,
-    #[serde(rename = "m_Enabled")]
+    m_editor_hide_flags: Option<serde_json::Value>,
+    #[serde(rename = "m_Enabled", default = "enabled")]
     m_enabled: i64,
     #[serde(rename = "m_GameObject")]
     m_game_object: MAvatar,
     #[serde(rename = "m_Name")]
     m_name: Option<serde_json::Value>,
     #[serde(rename = "m_ObjectHideFlags")]
-    m_object_hide_flags: i64,
+    m_object_hide_flags: Option<serde_json::Value>,
     #[serde(rename = "m_PrefabAsset")]
-    m_prefab_asset: MAvatar,
+    m_prefab_asset: Option<serde_json::Value>,
     #[serde(rename = "m_PrefabInstance")]
-    m_prefab_instance: MAvatar,
-    #[serde(rename = "m_Script")]
+    m_prefab_instance: Option<serde_json::Value>,
+    #[serde(rename = "m_Script", default)]
     m_script: MController,
-    particle_roots: Option<Vec<Option<serde_json::Value>>>,
-    #[serde(rename = "pauseUpdateUI")]
-    pause_update_ui: Option<i64>,
-    position_root: Option<MAvatar>,
-    shadow: Option<MAvatar>,
-    shoot_point: Option<MAvatar>,
-    sprites: Option<Vec<MAvatar>>,
-    target: Option<MAvatar>,
-    torso: Option<MAvatar>,
-    unit_id: Option<i64>,
-    sheet_data: Option<MController>,
-    sprite_renderer: Option<MAvatar>,
-    unit_controller: Option<MAvatar>,
+    /// Field do script khai báo (unit_id, sprites, sheet_data...), giữ nguyên cây property
+    #[serde(flatten)]
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl MonoBehaviour {
+    fn int_field(&self, key: &str) -> Option<i64> {
+        self.fields.get(key)?.as_i64()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -173,115 +179,55 @@ pub struct SimplifiedMonoBehaviour {
     is_ui: Option<i64>,
     pause_update_ui: Option<i64>,
     script_guid: String,
+    /// Class/namespace/assembly of the script, when the GUID resolves
+    script: Option<ScriptInfo>,
+    enabled: bool,
+    /// Every serialized field of the script; references carry a resolved `target`
+    fields: serde_json::Value,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SpriteRenderer {
-    #[serde(rename = "m_AdaptiveModeThreshold")]
-    m_adaptive_mode_threshold: f64,
-    #[serde(rename = "m_AutoUVMaxAngle")]
-    m_auto_uv_max_angle: i64,
-    #[serde(rename = "m_AutoUVMaxDistance")]
-    m_auto_uv_max_distance: f64,
-    #[serde(rename = "m_CastShadows")]
-    m_cast_shadows: i64,
-    #[serde(rename = "m_Color")]
+    #[serde(rename = "m_Color", default)]
     m_color: MColor,
-    #[serde(rename = "m_CorrespondingSourceObject")]
-    m_corresponding_source_object: MAvatar,
-    #[serde(rename = "m_DrawMode")]
+    #[serde(rename = "m_DrawMode", default)]
     m_draw_mode: i64,
-    #[serde(rename = "m_DynamicOccludee")]
-    m_dynamic_occludee: i64,
-    #[serde(rename = "m_Enabled")]
-    m_enabled: i64,
-    #[serde(rename = "m_FlipX")]
+    #[serde(rename = "m_FlipX", default)]
     m_flip_x: i64,
-    #[serde(rename = "m_FlipY")]
+    #[serde(rename = "m_FlipY", default)]
     m_flip_y: i64,
     #[serde(rename = "m_GameObject")]
     m_game_object: MAvatar,
-    #[serde(rename = "m_IgnoreNormalsForChartDetection")]
-    m_ignore_normals_for_chart_detection: i64,
-    #[serde(rename = "m_ImportantGI")]
-    m_important_gi: i64,
-    #[serde(rename = "m_LightProbeUsage")]
-    m_light_probe_usage: i64,
-    #[serde(rename = "m_LightProbeVolumeOverride")]
-    m_light_probe_volume_override: MAvatar,
-    #[serde(rename = "m_LightmapParameters")]
-    m_lightmap_parameters: MAvatar,
-    #[serde(rename = "m_MaskInteraction")]
-    m_mask_interaction: i64,
-    #[serde(rename = "m_Materials")]
-    m_materials: Vec<MController>,
-    #[serde(rename = "m_MinimumChartSize")]
-    m_minimum_chart_size: i64,
-    #[serde(rename = "m_MotionVectors")]
-    m_motion_vectors: i64,
-    #[serde(rename = "m_ObjectHideFlags")]
-    m_object_hide_flags: i64,
-    #[serde(rename = "m_PrefabAsset")]
-    m_prefab_asset: MAvatar,
-    #[serde(rename = "m_PrefabInstance")]
-    m_prefab_instance: MAvatar,
-    #[serde(rename = "m_PreserveUVs")]
-    m_preserve_u_vs: i64,
-    #[serde(rename = "m_ProbeAnchor")]
-    m_probe_anchor: MAvatar,
-    #[serde(rename = "m_RayTraceProcedural")]
-    m_ray_trace_procedural: i64,
-    #[serde(rename = "m_RayTracingMode")]
-    m_ray_tracing_mode: i64,
-    #[serde(rename = "m_ReceiveGI")]
-    m_receive_gi: i64,
-    #[serde(rename = "m_ReceiveShadows")]
-    m_receive_shadows: i64,
-    #[serde(rename = "m_ReflectionProbeUsage")]
-    m_reflection_probe_usage: i64,
-    #[serde(rename = "m_RendererPriority")]
-    m_renderer_priority: i64,
-    #[serde(rename = "m_RenderingLayerMask")]
-    m_rendering_layer_mask: i64,
-    #[serde(rename = "m_ScaleInLightmap")]
-    m_scale_in_lightmap: i64,
-    #[serde(rename = "m_SelectedEditorRenderState")]
-    m_selected_editor_render_state: i64,
-    #[serde(rename = "m_Size")]
+    #[serde(rename = "m_Size", default)]
     m_size: MSize,
-    #[serde(rename = "m_SortingLayer")]
-    m_sorting_layer: i64,
-    #[serde(rename = "m_SortingLayerID")]
-    m_sorting_layer_id: i64,
-    #[serde(rename = "m_SortingOrder")]
+    #[serde(rename = "m_SortingOrder", default)]
     m_sorting_order: i64,
-    #[serde(rename = "m_Sprite")]
+    #[serde(rename = "m_Sprite", default)]
     m_sprite: MController,
-    #[serde(rename = "m_SpriteSortPoint")]
-    m_sprite_sort_point: i64,
-    #[serde(rename = "m_SpriteTileMode")]
+    #[serde(rename = "m_SpriteTileMode", default)]
     m_sprite_tile_mode: i64,
-    #[serde(rename = "m_StaticBatchInfo")]
-    m_static_batch_info: MStaticBatchInfo,
-    #[serde(rename = "m_StaticBatchRoot")]
-    m_static_batch_root: MAvatar,
-    #[serde(rename = "m_StaticShadowCaster")]
-    m_static_shadow_caster: i64,
-    #[serde(rename = "m_StitchLightmapSeams")]
-    m_stitch_lightmap_seams: i64,
-    #[serde(rename = "m_WasSpriteAssigned")]
-    m_was_sprite_assigned: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct MColor {
-    a: f64,
-    b: f64,
-    g: f64,
-    r: f64,
+    pub a: f64,
+    pub b: f64,
+    pub g: f64,
+    pub r: f64,
+}
+
+impl Default for MColor {
+    fn default() -> Self {
+        MColor {
+            a: 1.0,
+            b: 1.0,
+            g: 1.0,
+            r: 1.0,
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct MSize {
     #[serde(rename = "serializedVersion", default)]
     pub serialized_version: Option<i64>,
@@ -289,61 +235,100 @@ pub struct MSize {
     pub y: f64,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct MStaticBatchInfo {
-    first_sub_mesh: i64,
-    sub_mesh_count: i64,
-}
-
 #[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
 pub struct Transform {
-    #[serde(rename = "m_Children")]
+    #[serde(rename = "m_Children", default)]
     m_children: Vec<MAvatar>,
-    #[serde(rename = "m_ConstrainProportionsScale")]
-    m_constrain_proportions_scale: i64,
-    #[serde(rename = "m_CorrespondingSourceObject")]
-    m_corresponding_source_object: MAvatar,
-    #[serde(rename = "m_Father")]
+    #[serde(rename = "m_Father", default)]
     m_father: MAvatar,
     #[serde(rename = "m_GameObject")]
     m_game_object: MAvatar,
-    #[serde(rename = "m_LocalEulerAnglesHint")]
+    #[serde(rename = "m_LocalEulerAnglesHint", default)]
     m_local_euler_angles_hint: MLocal,
-    #[serde(rename = "m_LocalPosition")]
+    #[serde(rename = "m_LocalPosition", default)]
     m_local_position: MLocal,
-    #[serde(rename = "m_LocalRotation")]
+    #[serde(rename = "m_LocalRotation", default = "MLocal::identity")]
     m_local_rotation: MLocal,
-    #[serde(rename = "m_LocalScale")]
+    #[serde(rename = "m_LocalScale", default = "MLocal::one")]
     m_local_scale: MLocal,
-    #[serde(rename = "m_ObjectHideFlags")]
-    m_object_hide_flags: i64,
-    #[serde(rename = "m_PrefabAsset")]
-    m_prefab_asset: MAvatar,
-    #[serde(rename = "m_PrefabInstance")]
-    m_prefab_instance: MAvatar,
-    #[serde(rename = "serializedVersion", default)]
-    serialized_version: Option<i64>,
+    #[serde(rename = "m_RootOrder", default)]
+    m_root_order: Option<i64>,
+}
+
+impl Transform {
+    /// Giữ liên kết cha/con khi typed view lỗi; giá trị đọc được thì dùng, còn lại mặc định
+    fn fallback(document: &UnityDocument) -> Self {
+        let local = |key: &str, default: MLocal| {
+            document
+                .body
+                .get(key)
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or(default)
+        };
+        let children = document
+            .body
+            .get("m_Children")
+            .and_then(|v| v.as_array())
+            .map(|children| {
+                children
+                    .iter()
+                    .filter_map(|c| c.get("fileID")?.as_i64())
+                    .map(|file_id| MAvatar { file_id })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Transform {
+            m_children: children,
+            m_father: MAvatar {
+                file_id: document.reference("m_Father").unwrap_or(0),
+            },
+            m_game_object: MAvatar {
+                file_id: document.reference("m_GameObject").unwrap_or(0),
+            },
+            m_local_euler_angles_hint: local("m_LocalEulerAnglesHint", MLocal::default()),
+            m_local_position: local("m_LocalPosition", MLocal::default()),
+            m_local_rotation: local("m_LocalRotation", MLocal::identity()),
+            m_local_scale: local("m_LocalScale", MLocal::one()),
+            m_root_order: document.body.get("m_RootOrder").and_then(|v| v.as_i64()),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct MLocal {
-    x: f64,
-    y: f64,
-    z: f64,
-    w: Option<f64>,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: Option<f64>,
+}
+
+impl MLocal {
+    fn identity() -> Self {
+        MLocal {
+            w: Some(1.0),
+            ..MLocal::default()
+        }
+    }
+
+    fn one() -> Self {
+        MLocal {
+            x: 1.0,
+            y: 1.0,
+            z: 1.0,
+            w: None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SimplifiedTransform {
-    local_position: MLocal,
-    local_scale: MLocal,
-    local_rotation: MLocal,
-    local_euler_angles_hint: MLocal,
-    father_id: i64,
-    children_ids: Vec<i64>,
+    pub local_position: MLocal,
+    pub local_scale: MLocal,
+    pub local_rotation: MLocal,
+    pub local_euler_angles_hint: MLocal,
+    pub father_id: i64,
+    pub children_ids: Vec<i64>,
 }
 
 // Struct để chứa thông tin Sprite Rect
@@ -358,22 +343,33 @@ pub struct SpriteRectInfo {
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SimplifiedSpriteRenderer {
-    color: MColor,
-    size: MSize,
-    sprite_guid: String,
-    sprite_base64: Option<String>,
-    sprite_rect: Option<SpriteRectInfo>,
-    sorting_order: i64,
-    offset: Vector2, // Đã có sẵn
-    flip_x: i64,
-    flip_y: i64,
+    pub color: MColor,
+    pub size: MSize,
+    pub sprite_guid: String,
+    /// fileID of the sprite inside `sprite_guid` (sprite sheet entry)
+    pub sprite_file_id: i64,
+    pub sprite_base64: Option<String>,
+    pub sprite_rect: Option<SpriteRectInfo>,
+    /// Normalized pivot inside `sprite_rect`
+    pub pivot: Option<Vector2>,
+    pub pixels_per_unit: Option<f32>,
+    /// Left, bottom, right, top in pixels (9-slice)
+    pub border: Option<Vector4>,
+    /// `m_DrawMode`: 0 = simple, 1 = sliced, 2 = tiled (drawn at `size`)
+    pub draw_mode: i64,
+    /// `m_SpriteTileMode`: 0 = continuous, 1 = adaptive
+    pub tile_mode: i64,
+    pub sorting_order: i64,
+    pub offset: Vector2, // Đã có sẵn
+    pub flip_x: i64,
+    pub flip_y: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SimplifiedAnimator {
-    enabled: i64,
-    controller_guid: String,
+    pub enabled: i64,
+    pub controller_guid: String,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -398,17 +394,28 @@ pub struct SimplifiedParticleSystem {
 
 #[derive(Serialize, Deserialize)]
 pub struct HierarchyNode {
-    name: String,
+    pub name: String,
     #[serde(rename = "type")]
-    r#type: String,
-    is_active: i64,
-    transform: Option<SimplifiedTransform>,
-    sprite_renderer: Option<SimplifiedSpriteRenderer>,
-    animator: Option<SimplifiedAnimator>,
-    particle_system: Option<SimplifiedParticleSystem>,
-    animation_clips: Option<Vec<String>>, // List of animation clip names
-    mono_behaviours: Vec<SimplifiedMonoBehaviour>,
-    children: Vec<HierarchyNode>,
+    pub r#type: String,
+    pub is_active: i64,
+    pub transform: Option<SimplifiedTransform>,
+    pub sprite_renderer: Option<SimplifiedSpriteRenderer>,
+    pub animator: Option<SimplifiedAnimator>,
+    pub particle_system: Option<SimplifiedParticleSystem>,
+    pub animation_clips: Option<Vec<String>>, // List of animation clip names
+    pub mono_behaviours: Vec<SimplifiedMonoBehaviour>,
+    /// Components without a typed view (colliders, UI, unknown classes...) as raw property trees
+    pub raw_components: Vec<UnityDocument>,
+    pub children: Vec<HierarchyNode>,
+    /// Local-to-world matrix, column major like Unity's `Matrix4x4`
+    pub world_matrix: [f64; 16],
+    /// World-space bounds of this node's sprite
+    pub sprite_bounds: Option<WorldBounds>,
+    /// Bounds of the active sprites in this subtree (on the root: the whole prefab)
+    pub bounds: Option<WorldBounds>,
+    /// Why a component of this node could not be read (bad document, missing sprite...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<UnityParseError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -479,7 +486,7 @@ pub struct VertexChannel {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VertexData {
-    #[serde(rename = "_typelessdata")]
+    #[serde(rename = "_typelessdata", default, deserialize_with = "hex_string")]
     pub typeless_data: Option<String>,
     #[serde(rename = "m_Channels")]
     pub channels: Vec<VertexChannel>,
@@ -548,12 +555,32 @@ pub struct RenderData {
     pub uv_transform: Vector4,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// `{first: <GUID>, second: <file ID>}`, also the key of `SpriteAtlas.m_RenderDataMap`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RenderDataKey {
-    // Key is a hash string, value is a large integer
-    // Using HashMap for flexible key handling
-    #[serde(flatten)]
-    pub data: std::collections::HashMap<String, i64>,
+    #[serde(deserialize_with = "guid_string")]
+    pub first: String,
+    pub second: i64,
+}
+
+// Hex toàn chữ số bị YAML đọc thành số (mất số 0 ở đầu), sprite_mesh đọc lại từ text
+fn hex_string<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(hex) => Some(hex),
+        serde_json::Value::Null => None,
+        other => Some(other.to_string()),
+    })
+}
+
+// GUID toàn chữ số bị YAML đọc thành số
+fn guid_string<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(guid) => guid,
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -625,112 +652,173 @@ struct SpriteRD {
 
 // Struct để chứa thông tin sprite với rect và offset
 #[derive(Debug, Clone)]
-struct SpriteInfo {
-    base64: Option<String>,
-    rect: SpriteRectInfo,
-    offset: Vector2, // Đã được bổ sung
-}
-
-// Hàm tìm đường dẫn file asset từ GUID (sprite hoặc texture)
-fn find_asset_path(guid: &str, project_path: &str, extension: &str) -> Option<String> {
-    let meta_patterns = vec![
-        format!(
-            "{}/Assets/00_Unit/#Image/**/*.{}.meta",
-            project_path, extension
-        ),
-        format!("{}/Assets/00_Unit/#Image/**/*.meta", project_path), // Cho .png.meta, .asset.meta
-        format!("{}/Assets/00_Unit/SpriteAtlas/**/*.meta", project_path), // Cho Sprite Atlas
-        format!("{}/Assets/**/*.{}.meta", project_path, extension),
-        format!("{}/Assets/**/*.meta", project_path), // Cho .png.meta, .asset.meta
-    ];
-    for pattern in meta_patterns {
-        if let Ok(meta_files) = glob(&pattern) {
-            for meta_file in meta_files.filter_map(Result::ok) {
-                if let Ok(meta_content) = fs::read_to_string(&meta_file) {
-                    if meta_content.contains(&format!("guid: {}", guid)) {
-                        let asset_path = meta_file.with_extension(""); // Bỏ .meta
-                        if asset_path.exists() {
-                            return Some(asset_path.to_string_lossy().to_string());
-                        }
-                    }
-                }
-            }
-        }
-    }
-    None
+pub(crate) struct SpriteInfo {
+    pub base64: Option<String>,
+    pub rect: SpriteRectInfo,
+    pub offset: Vector2, // Đã được bổ sung
+    pub pivot: Vector2,
+    pub pixels_per_unit: f32,
+    pub border: Vector4,
+    /// Problems that did not stop the sprite from loading (missing alpha texture...)
+    pub diagnostics: Vec<UnityParseError>,
 }
 
-// Cập nhật hàm sprite_to_base64 để trả về cả base64, rect info và offset
-fn sprite_to_base64_with_rect(sprite_guid: &str, project_path: &str) -> Option<SpriteInfo> {
-    // Tìm Sprite.asset hoặc .png từ GUID
-    let sprite_path = find_asset_path(sprite_guid, project_path, "asset")
-        .or_else(|| find_asset_path(sprite_guid, project_path, "png"))
-        .or_else(|| find_asset_path(sprite_guid, project_path, "jpg"))?;
-
-    if sprite_path.ends_with(".png") || sprite_path.ends_with(".jpg") {
-        // Nếu là ảnh trực tiếp, encode base64 và lấy kích thước
-        let base64 = image_to_base64(&sprite_path);
-        let rect = get_image_dimensions(&sprite_path)?;
-        return Some(SpriteInfo {
-            base64,
-            rect,
-            offset: Vector2 { x: 0.0, y: 0.0 }, // Offset mặc định cho ảnh trực tiếp
-        });
+// Hàm tìm đường dẫn file asset từ GUID (sprite hoặc texture) qua GUID index của project
+fn find_asset_path(guid: &str, index: &GuidIndex, extension: &str) -> Option<String> {
+    let asset_path = index.resolve_str(guid)?;
+    if asset_path.ends_with(&format!(".{}", extension)) {
+        Some(asset_path)
+    } else {
+        None
     }
+}
 
-    // Xử lý .asset (Sprite Atlas)
-    let sprite_content = fs::read_to_string(&sprite_path).ok()?;
-    let yaml = YamlLoader::load_from_str(&sprite_content).ok()?;
-
-    if yaml.is_empty() {
-        return None;
-    }
+// File ID của document Sprite trong Sprite.asset
+pub(crate) const SPRITE_FILE_ID: i64 = 21300000;
 
-    let json = yaml_to_json(&yaml[3])
-        .map_err(|e| {
-            println!("yaml_to_json error for sprite GUID {}: {}", sprite_guid, e);
-            "Failed to convert YAML to JSON"
+/// Sprite đã decode, dùng cho compositor
+#[derive(Debug, Clone)]
+pub(crate) struct LoadedSprite {
+    /// Pixels of the sprite's texture rect (top-left origin)
+    pub image: RgbaImage,
+    pub rect: SpriteRectInfo,
+    pub offset: Vector2,
+    /// Normalized pivot inside `rect` (0.5, 0.5 = center)
+    pub pivot: Vector2,
+    pub pixels_per_unit: f32,
+    /// Left, bottom, right, top in pixels, for 9-slicing
+    pub border: Vector4,
+    /// Position of `image` inside `rect` when the sprite is packed tight
+    pub texture_rect_offset: Vector2,
+    /// Problems that did not stop the sprite from loading (missing alpha texture...)
+    pub diagnostics: Vec<UnityParseError>,
+}
+
+// Đọc Sprite.asset theo GUID, trả kèm text gốc của file
+fn read_sprite_asset(
+    sprite_path: &str,
+    sprite_guid: &str,
+) -> Result<(SpriteAsset, String), UnityParseError> {
+    let yaml_error = |message: String| UnityParseError::Yaml {
+        file_id: SPRITE_FILE_ID,
+        message,
+    };
+    let sprite_content = fs::read_to_string(sprite_path)
+        .map_err(|e| yaml_error(format!("Failed to read sprite {}: {}", sprite_guid, e)))?;
+    let yaml = YamlLoader::load_from_str(&sprite_content).map_err(|e| yaml_error(e.to_string()))?;
+    let document = yaml
+        .get(3)
+        .ok_or_else(|| yaml_error(format!("Sprite {} has no Sprite document", sprite_guid)))?;
+
+    let json = yaml_to_json(document).map_err(yaml_error)?;
+    let sprite_asset = deserialize_document(SPRITE_FILE_ID, &json)?;
+    Ok((sprite_asset, sprite_content))
+}
+
+fn find_sprite_path(sprite_guid: &str, index: &GuidIndex) -> Result<String, UnityParseError> {
+    find_asset_path(sprite_guid, index, "asset")
+        .or_else(|| find_asset_path(sprite_guid, index, "png"))
+        .or_else(|| find_asset_path(sprite_guid, index, "jpg"))
+        .ok_or_else(|| UnityParseError::MissingGuid {
+            guid: sprite_guid.to_string(),
         })
-        .ok()?;
-
-    let sprite_asset: SpriteAsset = match serde_json::from_value::<SpriteAsset>(json.clone()) {
-        Ok(s) => s,
-        Err(e) => {
-            println!(
-                "Failed to parse SpriteAsset JSON for GUID {}: {}",
-                sprite_guid, e
-            );
+}
 
-            // Log thêm thông tin chi tiết về vị trí lỗi
-            println!("Error at line: {:?}", e.line());
-            println!("Error at column: {:?}", e.column());
+fn decode_texture(
+    texture_path: &str,
+    sprite_guid: &str,
+    texture_guid: Option<&String>,
+) -> Result<RgbaImage, UnityParseError> {
+    ImageReader::open(texture_path)
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.decode().map_err(|e| e.to_string()))
+        .map(|img| img.to_rgba8())
+        .map_err(|message| UnityParseError::MissingTexture {
+            sprite_guid: sprite_guid.to_string(),
+            texture_guid: texture_guid.cloned(),
+            message,
+        })
+}
 
-            // Log JSON gốc để debug
-            println!(
-                "Original JSON: {}",
-                serde_json::to_string_pretty(&json).unwrap_or_else(|_| "Invalid JSON".to_string())
-            );
+/// Decode a sprite (Sprite.asset, a sprite sheet entry or a plain texture) into pixels
+/// with its pivot and PPU. `file_id` picks the sub-sprite of a sheet
+pub(crate) fn load_sprite(
+    sprite_guid: &str,
+    file_id: i64,
+    index: &GuidIndex,
+) -> Result<LoadedSprite, UnityParseError> {
+    let sprite_path = find_sprite_path(sprite_guid, index)?;
 
-            return None;
+    if sprite_path.ends_with(".png") || sprite_path.ends_with(".jpg") {
+        // Ảnh trực tiếp: pivot, PPU và border lấy từ TextureImporter trong .meta
+        let image = decode_texture(&sprite_path, sprite_guid, None)?;
+        let (width, height) = image.dimensions();
+        let importer = TextureImporter::read(&sprite_path).unwrap_or_default();
+        // Sprite sheet (spriteMode 2): fileID trỏ vào một sprite con của texture
+        if let Some(sheet) = importer.sheet_sprite(file_id) {
+            return Ok(LoadedSprite {
+                image: crop_sheet_sprite(&image, sheet),
+                rect: SpriteRectInfo {
+                    x: sheet.rect.x,
+                    y: sheet.rect.y,
+                    width: sheet.rect.width,
+                    height: sheet.rect.height,
+                },
+                offset: pivot_offset(sheet.rect.width, sheet.rect.height, &sheet.pivot),
+                pivot: sheet.pivot.clone(),
+                pixels_per_unit: importer.pixels_per_unit,
+                border: sheet.border.clone(),
+                texture_rect_offset: Vector2 { x: 0.0, y: 0.0 },
+                diagnostics: Vec::new(),
+            });
         }
-    };
-
-    // Lấy texture GUID từ m_RD.texture
-    let texture_guid = sprite_asset.sprite.rd.texture.guid;
-    if texture_guid.is_none() {
-        return None;
+        return Ok(LoadedSprite {
+            image,
+            rect: SpriteRectInfo {
+                x: 0.0,
+                y: 0.0,
+                width: width as f32,
+                height: height as f32,
+            },
+            offset: pivot_offset(width as f32, height as f32, &importer.pivot),
+            pivot: importer.pivot,
+            pixels_per_unit: importer.pixels_per_unit,
+            border: importer.border,
+            texture_rect_offset: Vector2 { x: 0.0, y: 0.0 },
+            diagnostics: Vec::new(),
+        });
     }
-    let texture_guid = texture_guid.unwrap(); // Unwrap to get the String
 
-    let texture_path = find_asset_path(&texture_guid, project_path, "png")
-        .or_else(|| find_asset_path(&texture_guid, project_path, "jpg"))?;
+    // Xử lý .asset (Sprite Atlas)
+    let (sprite_asset, sprite_content) = read_sprite_asset(&sprite_path, sprite_guid)?;
+    let sprite = sprite_asset.sprite;
+
+    // m_RD.texture, không có thì lấy texture đã pack trong SpriteAtlas
+    let packed = packed_texture(&sprite, sprite_guid, index).ok_or_else(|| {
+        UnityParseError::MissingTexture {
+            sprite_guid: sprite_guid.to_string(),
+            texture_guid: sprite.rd.texture.guid.clone(),
+            message: "Texture not found in project or sprite atlas".to_string(),
+        }
+    })?;
 
     // Load texture image
-    let reader = ImageReader::open(&texture_path).ok()?;
-    let img = reader.decode().ok()?;
+    let mut img = decode_texture(
+        &packed.texture_path,
+        sprite_guid,
+        Some(&packed.texture_guid),
+    )?;
+    // ETC1: màu và alpha nằm ở hai texture riêng; thiếu alpha thì vẫn vẽ sprite (đục)
+    let mut diagnostics = Vec::new();
+    if let Some((alpha_guid, alpha_path)) = &packed.alpha_texture {
+        match decode_texture(alpha_path, sprite_guid, Some(alpha_guid)) {
+            Ok(alpha) => merge_alpha(&mut img, &alpha),
+            Err(e) => diagnostics.push(e),
+        }
+    }
 
     // Crop theo textureRect (pixels)
-    let rect = sprite_asset.sprite.rd.texture_rect;
+    let rect = &packed.texture_rect;
     let x = rect.x as u32;
     let mut y = rect.y as u32;
     let w = rect.width as u32;
@@ -738,40 +826,101 @@ fn sprite_to_base64_with_rect(sprite_guid: &str, project_path: &str) -> Option<S
 
     // Flip y vì Unity UV bottom-left, image top-left
     let texture_height = img.height();
-    y = texture_height - (y + h);
+    y = texture_height.saturating_sub(y + h);
 
     // Crop
-    let cropped = img.crop_imm(x, y, w, h);
+    let cropped = image::imageops::crop_imm(&img, x, y, w, h).to_image();
+    let mut cropped = unpack_rotation(cropped, packed.settings_raw);
+
+    // Tight/polygon: textureRect còn chứa pixel của sprite khác, chỉ giữ phần trong mesh
+    if needs_mesh_mask(packed.settings_raw, sprite.is_polygon != 0) {
+        if let Some(triangles) = mesh_triangles(&sprite.rd, &sprite_content) {
+            let origin = (
+                sprite.rect.width * sprite.pivot.x - packed.texture_rect_offset.x,
+                sprite.rect.height * sprite.pivot.y - packed.texture_rect_offset.y,
+            );
+            mask_to_mesh(&mut cropped, &triangles, origin, sprite.pixels_to_units);
+        }
+    }
+
+    Ok(LoadedSprite {
+        image: cropped,
+        // Tạo SpriteRectInfo từ m_Rect của sprite (không phải textureRect)
+        rect: SpriteRectInfo {
+            x: sprite.rect.x,
+            y: sprite.rect.y,
+            width: sprite.rect.width,
+            height: sprite.rect.height,
+        },
+        // Lấy offset từ sprite asset - ĐÂY LÀ ĐIỂM QUAN TRỌNG
+        offset: sprite.offset,
+        pivot: sprite.pivot,
+        pixels_per_unit: sprite.pixels_to_units,
+        border: sprite.border,
+        texture_rect_offset: packed.texture_rect_offset,
+        diagnostics,
+    })
+}
+
+// Cập nhật hàm sprite_to_base64 để trả về cả base64, rect info và offset
+pub(crate) fn sprite_to_base64_with_rect(
+    sprite_guid: &str,
+    file_id: i64,
+    index: &GuidIndex,
+) -> Result<SpriteInfo, UnityParseError> {
+    // Tìm Sprite.asset hoặc .png từ GUID
+    let sprite_path = find_sprite_path(sprite_guid, index)?;
+    let is_image = sprite_path.ends_with(".png") || sprite_path.ends_with(".jpg");
+    // Pivot/PPU/border theo TextureImporter, offset suy ra từ pivot như Sprite.asset
+    let importer = is_image.then(|| TextureImporter::read(&sprite_path).unwrap_or_default());
+
+    // Sprite con của sheet phải crop, đi qua load_sprite
+    if let Some(importer) = importer.filter(|i| i.sheet_sprite(file_id).is_none()) {
+        // Nếu là ảnh trực tiếp, encode base64 và lấy kích thước
+        let base64 = image_to_base64(&sprite_path);
+        let rect = get_image_dimensions(&sprite_path, sprite_guid)?;
+        return Ok(SpriteInfo {
+            base64,
+            offset: pivot_offset(rect.width, rect.height, &importer.pivot),
+            rect,
+            pivot: importer.pivot,
+            pixels_per_unit: importer.pixels_per_unit,
+            border: importer.border,
+            diagnostics: Vec::new(),
+        });
+    }
+
+    let sprite = load_sprite(sprite_guid, file_id, index)?;
 
     // Encode to base64
     let mut buf = std::io::Cursor::new(Vec::new());
-    cropped.write_to(&mut buf, image::ImageFormat::Png).ok()?;
-    let base64_str = general_purpose::STANDARD.encode(buf.get_ref());
-    let base64 = Some(format!("data:image/png;base64,{}", base64_str));
-
-    // Tạo SpriteRectInfo từ m_Rect của sprite (không phải textureRect)
-    let sprite_rect = SpriteRectInfo {
-        x: sprite_asset.sprite.rect.x,
-        y: sprite_asset.sprite.rect.y,
-        width: sprite_asset.sprite.rect.width,
-        height: sprite_asset.sprite.rect.height,
-    };
-
-    // Lấy offset từ sprite asset - ĐÂY LÀ ĐIỂM QUAN TRỌNG
-    let sprite_offset = sprite_asset.sprite.offset;
+    let base64 = sprite
+        .image
+        .write_to(&mut buf, image::ImageFormat::Png)
+        .ok()
+        .map(|_| {
+            let base64_str = general_purpose::STANDARD.encode(buf.get_ref());
+            format!("data:image/png;base64,{}", base64_str)
+        });
 
-    Some(SpriteInfo {
+    Ok(SpriteInfo {
         base64,
-        rect: sprite_rect,
-        offset: sprite_offset, // Sử dụng offset từ sprite asset
+        rect: sprite.rect,
+        offset: sprite.offset, // Sử dụng offset từ sprite asset
+        pivot: sprite.pivot,
+        pixels_per_unit: sprite.pixels_per_unit,
+        border: sprite.border,
+        diagnostics: sprite.diagnostics,
     })
 }
 
 // Hàm lấy kích thước ảnh trực tiếp
-fn get_image_dimensions(file_path: &str) -> Option<SpriteRectInfo> {
-    let reader = ImageReader::open(file_path).ok()?;
-    let img = reader.decode().ok()?;
-    Some(SpriteRectInfo {
+fn get_image_dimensions(
+    file_path: &str,
+    sprite_guid: &str,
+) -> Result<SpriteRectInfo, UnityParseError> {
+    let img = decode_texture(file_path, sprite_guid, None)?;
+    Ok(SpriteRectInfo {
         x: 0.0,
         y: 0.0,
         width: img.width() as f32,
@@ -796,64 +945,84 @@ fn image_to_base64(file_path: &str) -> Option<String> {
 
 pub type Prefab = Vec<PrefabElement>;
 
-async fn read_prefab(prefab_path: String) -> Result<Prefab, String> {
-    let prefab_content = fs::read_to_string(&prefab_path)
-        .map_err(|e| format!("Failed to read prefab file: {}", e))?;
-
-    let mut prefab_elements = Vec::new();
-    let lines: Vec<&str> = prefab_content.lines().collect();
-    let mut i = 0;
-    while i < lines.len() {
-        if lines[i].starts_with("--- !u!") {
-            let header = lines[i];
-            let parts: Vec<&str> = header.split_whitespace().collect();
-            if parts.len() != 3 {
-                i += 1;
-                continue;
-            }
-            let id_str = parts[2].trim_start_matches('&');
-            let id: i64 = match id_str.parse() {
-                Ok(num) => num,
-                Err(_) => {
-                    i += 1;
-                    continue;
-                }
-            };
-            let mut body_lines = Vec::new();
-            i += 1;
-            while i < lines.len() && !lines[i].starts_with("--- !u!") {
-                body_lines.push(lines[i]);
-                i += 1;
-            }
-            let body = body_lines.join("\n");
-            let docs = match YamlLoader::load_from_str(&body) {
-                Ok(d) => d,
-                Err(_) => {
-                    continue;
-                }
-            };
-            if docs.is_empty() {
-                continue;
+pub(crate) async fn read_prefab(prefab_path: String, index: &GuidIndex) -> Result<Prefab, String> {
+    // PrefabInstance (nested prefab, variant) được gộp vào trước khi deserialize
+    let documents = load_merged_documents(Path::new(&prefab_path), index)?;
+    Ok(documents.into_iter().map(prefab_element).collect())
+}
+
+// Typed view theo class ID; class chưa hỗ trợ hoặc lỗi schema thì giữ document gốc
+fn prefab_element(document: UnityDocument) -> PrefabElement {
+    let mut element = PrefabElement::empty(document.file_id);
+    let typed = match document.class_id {
+        CLASS_GAME_OBJECT => Some(document.view().map(|v| element.game_object = Some(v))),
+        CLASS_TRANSFORM | CLASS_RECT_TRANSFORM => {
+            Some(document.view().map(|v| element.transform = Some(v)))
+        }
+        CLASS_ANIMATOR => Some(document.view().map(|v| element.animator = Some(v))),
+        CLASS_MONO_BEHAVIOUR => Some(document.view().map(|v| element.mono_behaviour = Some(v))),
+        CLASS_PARTICLE_SYSTEM => Some(document.view().map(|v| element.particle_system = Some(v))),
+        CLASS_SPRITE_RENDERER => Some(document.view().map(|v| element.sprite_renderer = Some(v))),
+        CLASS_SCENE_ROOTS => Some(document.view().map(|v| element.scene_roots = Some(v))),
+        _ => None,
+    };
+    // GameObject/Transform lỗi vẫn phải tạo node, nếu không cả nhánh con biến mất khỏi cây
+    if matches!(typed, Some(Err(_))) {
+        match document.class_id {
+            CLASS_GAME_OBJECT => element.game_object = Some(GameObject::fallback(&document)),
+            CLASS_TRANSFORM | CLASS_RECT_TRANSFORM
+                if document.reference("m_GameObject").is_some() =>
+            {
+                element.transform = Some(Transform::fallback(&document))
             }
-            let yaml = &docs[0];
-            let json = match yaml_to_json(yaml) {
-                Ok(j) => j,
-                Err(_) => continue,
-            };
-            let mut element: PrefabElement = match serde_json::from_value(json) {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-            element.id = id;
-            prefab_elements.push(element);
-        } else {
-            i += 1;
+            _ => {}
         }
     }
-    Ok(prefab_elements)
+
+    let error = match &typed {
+        Some(result) => result.clone().err(),
+        None if document.error.is_some() => document.error.clone(),
+        None if class_name(document.class_id).is_none() => Some(UnityParseError::UnknownClassId {
+            class_id: document.class_id,
+            file_id: document.file_id,
+            type_name: document.type_name.clone(),
+        }),
+        None => None,
+    };
+    element.type_name = document.type_name.clone();
+    element.game_object_id = document.reference("m_GameObject");
+    if let Some(error) = error {
+        element.diagnostic = Some(DocumentDiagnostic {
+            file_id: document.file_id,
+            class_id: document.class_id,
+            game_object_id: document.reference("m_GameObject"),
+            error,
+        });
+    }
+    if !matches!(typed, Some(Ok(()))) {
+        element.raw = Some(document);
+    }
+    element
+}
+
+/// Every document-level problem found while reading the file
+pub(crate) fn prefab_diagnostics(prefab: &Prefab) -> Vec<DocumentDiagnostic> {
+    prefab
+        .iter()
+        .filter_map(|el| el.diagnostic.clone())
+        .collect()
 }
 
-fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
+/// Root GameObject of a prefab (the first root when the file has several)
+pub(crate) fn build_hierarchy(prefab: &Prefab, index: &GuidIndex) -> Result<HierarchyNode, String> {
+    build_forest(prefab, index)
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No root GameObject found in prefab".to_string())
+}
+
+/// Every root GameObject of a prefab or scene, ordered like the Unity hierarchy window
+pub(crate) fn build_forest(prefab: &Prefab, index: &GuidIndex) -> Vec<HierarchyNode> {
     let mut id_to_go: HashMap<i64, &GameObject> = HashMap::new();
     let mut id_to_trans: HashMap<i64, &Transform> = HashMap::new();
     let mut trans_id_to_go_id: HashMap<i64, i64> = HashMap::new();
@@ -862,9 +1031,25 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
     let mut id_to_animator: HashMap<i64, &Animator> = HashMap::new();
     let mut id_to_particle: HashMap<i64, &ParticleSystem> = HashMap::new();
     let mut id_to_monos: HashMap<i64, Vec<&MonoBehaviour>> = HashMap::new();
+    let mut scene_roots: Vec<i64> = Vec::new();
+    let mut id_to_diagnostics: HashMap<i64, Vec<UnityParseError>> = HashMap::new();
+    let mut id_to_raw: HashMap<i64, Vec<&UnityDocument>> = HashMap::new();
 
     for el in prefab {
         let id = el.id;
+        if let Some(diagnostic) = &el.diagnostic {
+            if let Some(go_id) = diagnostic.game_object_id {
+                id_to_diagnostics
+                    .entry(go_id)
+                    .or_default()
+                    .push(diagnostic.error.clone());
+            }
+        }
+        if let Some(raw) = &el.raw {
+            if let Some(go_id) = raw.reference("m_GameObject") {
+                id_to_raw.entry(go_id).or_default().push(raw);
+            }
+        }
         if let Some(go) = &el.game_object {
             id_to_go.insert(id, go);
         }
@@ -890,19 +1075,78 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
             let go_id = mono.m_game_object.file_id;
             id_to_monos.entry(go_id).or_insert(Vec::new()).push(mono);
         }
+        if let Some(roots) = &el.scene_roots {
+            scene_roots.extend(roots.m_roots.iter().map(|r| r.file_id));
+        }
     }
-    let root_trans: Vec<i64> = id_to_trans
+
+    // Mỗi script GUID chỉ resolve một lần cho cả prefab
+    let scripts = resolve_scripts(
+        id_to_monos
+            .values()
+            .flatten()
+            .filter_map(|mono| mono.m_script.guid.as_deref()),
+        index,
+    );
+
+    // Tên object mà `{fileID: ...}` trong field của script trỏ tới
+    let locals: HashMap<i64, LocalObject> = prefab
         .iter()
-        .filter_map(|(&tid, trans)| {
-            if trans.m_father.file_id == 0 {
-                Some(tid)
-            } else {
-                None
-            }
+        .map(|el| {
+            let go_id = el.game_object_id.unwrap_or(el.id);
+            let name = id_to_go.get(&go_id).map(|go| go.m_name.clone());
+            (
+                el.id,
+                LocalObject {
+                    type_name: el.type_name.clone(),
+                    name,
+                },
+            )
         })
         .collect();
-    let root_trans_id = root_trans[0];
-    let root_go_id = trans_id_to_go_id[&root_trans_id];
+    let id_to_mono_views: HashMap<i64, Vec<SimplifiedMonoBehaviour>> = id_to_monos
+        .iter()
+        .map(|(&go_id, monos)| {
+            let views = monos
+                .iter()
+                .map(|mono| SimplifiedMonoBehaviour {
+                    unit_id: mono.int_field("unit_id"),
+                    is_ui: mono.int_field("isUI"),
+                    pause_update_ui: mono.int_field("pauseUpdateUI"),
+                    script_guid: mono.m_script.guid.clone().unwrap_or_default(),
+                    script: mono
+                        .m_script
+                        .guid
+                        .as_ref()
+                        .and_then(|guid| scripts.get(guid))
+                        .cloned(),
+                    enabled: mono.m_enabled != 0,
+                    fields: resolve_references(
+                        &serde_json::Value::Object(mono.fields.clone()),
+                        &locals,
+                        index,
+                    ),
+                })
+                .collect();
+            (go_id, views)
+        })
+        .collect();
+
+    // Duyệt theo thứ tự document để kết quả ổn định, sau đó sắp theo SceneRoots/m_RootOrder
+    let mut root_trans: Vec<(usize, &Transform, i64)> = prefab
+        .iter()
+        .enumerate()
+        .filter_map(|(i, el)| Some((i, el.transform.as_ref()?, el.id)))
+        .filter(|(_, trans, _)| trans.m_father.file_id == 0)
+        .collect();
+    root_trans.sort_by_key(|&(i, trans, tid)| {
+        let scene_order = scene_roots.iter().position(|&r| r == tid);
+        (
+            scene_order.unwrap_or(usize::MAX),
+            trans.m_root_order.unwrap_or(i64::MAX),
+            i,
+        )
+    });
 
     fn build_node(
         go_id: i64,
@@ -913,10 +1157,13 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
         id_to_sprite: &HashMap<i64, &SpriteRenderer>,
         id_to_animator: &HashMap<i64, &Animator>,
         id_to_particle: &HashMap<i64, &ParticleSystem>,
-        id_to_monos: &HashMap<i64, Vec<&MonoBehaviour>>,
-        project_path: &str,
-    ) -> HierarchyNode {
-        let go = id_to_go.get(&go_id).unwrap();
+        id_to_monos: &HashMap<i64, Vec<SimplifiedMonoBehaviour>>,
+        id_to_diagnostics: &HashMap<i64, Vec<UnityParseError>>,
+        id_to_raw: &HashMap<i64, Vec<&UnityDocument>>,
+        index: &GuidIndex,
+    ) -> Option<HierarchyNode> {
+        let go = id_to_go.get(&go_id)?;
+        let mut diagnostics = id_to_diagnostics.get(&go_id).cloned().unwrap_or_default();
         let name = go.m_name.clone();
         let r#type = "GameObject".to_string();
         let is_active = go.m_is_active;
@@ -942,25 +1189,41 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
             let sprite_guid = sr.m_sprite.guid.clone();
 
             // Sử dụng hàm đã cập nhật để lấy cả base64, rect info và offset
-            let sprite_info = if let Some(guid) = &sprite_guid {
-                sprite_to_base64_with_rect(guid, project_path)
-            } else {
-                None
+            let sprite_info = match &sprite_guid {
+                Some(guid) => sprite_to_base64_with_rect(guid, sr.m_sprite.file_id, index)
+                    .map_err(|e| diagnostics.push(e))
+                    .ok(),
+                None => None,
             };
 
-            let (sprite_base64, sprite_rect, sprite_offset) = if let Some(info) = sprite_info {
-                (info.base64, Some(info.rect), info.offset)
-            } else {
-                (None, None, Vector2 { x: 0.0, y: 0.0 })
-            };
+            let (sprite_base64, sprite_rect, sprite_offset, pivot, pixels_per_unit, border) =
+                if let Some(info) = sprite_info {
+                    diagnostics.extend(info.diagnostics);
+                    (
+                        info.base64,
+                        Some(info.rect),
+                        info.offset,
+                        Some(info.pivot),
+                        Some(info.pixels_per_unit),
+                        Some(info.border),
+                    )
+                } else {
+                    (None, None, Vector2 { x: 0.0, y: 0.0 }, None, None, None)
+                };
 
             Some(SimplifiedSpriteRenderer {
                 color: sr.m_color.clone(),
                 size: sr.m_size.clone(),
                 offset: sprite_offset, // Sử dụng offset từ sprite asset thay vì default
                 sprite_guid: sprite_guid.unwrap_or_default(),
+                sprite_file_id: sr.m_sprite.file_id,
                 sprite_base64,
                 sprite_rect, // Thông tin sprite rect
+                pivot,
+                pixels_per_unit,
+                border,
+                draw_mode: sr.m_draw_mode,
+                tile_mode: sr.m_sprite_tile_mode,
                 sorting_order: sr.m_sorting_order,
                 flip_x: sr.m_flip_x,
                 flip_y: sr.m_flip_y,
@@ -978,6 +1241,12 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
             None
         };
 
+        // Lấy danh sách clip từ AnimatorController mà Animator tham chiếu
+        let animation_clips = animator
+            .as_ref()
+            .filter(|a| !a.controller_guid.is_empty())
+            .and_then(|a| controller_clip_names(&a.controller_guid, index));
+
         let particle_system = if let Some(ps) = id_to_particle.get(&go_id) {
             Some(SimplifiedParticleSystem {
                 enabled: ps.m_enabled,
@@ -987,31 +1256,23 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
                 prewarm: ps.prewarm,
                 play_on_awake: ps.playOnAwake,
                 auto_random_seed: ps.autoRandomSeed,
-                start_lifetime: ps.startLifetime.scalar.unwrap_or(0.0),
-                start_speed: ps.startSpeed.scalar.unwrap_or(0.0),
-                start_size: ps.startSizeX.scalar.unwrap_or(0.0), // Using X size
-                start_color: ps.startColor.maxColor.clone(),
-                gravity_modifier: ps.gravityModifier.scalar.unwrap_or(0.0),
+                start_lifetime: ps.initial().startLifetime.scalar.unwrap_or(0.0),
+                start_speed: ps.initial().startSpeed.scalar.unwrap_or(0.0),
+                start_size: ps.initial().startSizeX.scalar.unwrap_or(0.0), // Using X size
+                start_color: ps.initial().startColor.maxColor.clone(),
+                gravity_modifier: ps.initial().gravityModifier.scalar.unwrap_or(0.0),
                 emission_rate: ps.emission.rateOverTime.scalar.unwrap_or(0.0),
                 shape_type: ps.shape.shape_type,
             })
         } else {
             None
         };
-        let mono_behaviours: Vec<SimplifiedMonoBehaviour> =
-            if let Some(monos) = id_to_monos.get(&go_id) {
-                monos
-                    .iter()
-                    .map(|mono| SimplifiedMonoBehaviour {
-                        unit_id: mono.unit_id,
-                        is_ui: mono.is_ui,
-                        pause_update_ui: mono.pause_update_ui,
-                        script_guid: mono.m_script.guid.clone().unwrap_or_default(),
-                    })
-                    .collect()
-            } else {
-                Vec::new()
-            };
+        let mono_behaviours = id_to_monos.get(&go_id).cloned().unwrap_or_default();
+        // Component chưa có typed view vẫn hiển thị dưới dạng cây property
+        let raw_components: Vec<UnityDocument> = id_to_raw
+            .get(&go_id)
+            .map(|docs| docs.iter().map(|d| (*d).clone()).collect())
+            .unwrap_or_default();
         let mut children = Vec::new();
         if let Some(trans_id) = go_id_to_trans_id.get(&go_id) {
             if let Some(trans) = id_to_trans.get(trans_id) {
@@ -1019,7 +1280,7 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
                     let child_trans_id = child.file_id;
                     let child_go_id = *trans_id_to_go_id.get(&child_trans_id).unwrap_or(&0);
                     if child_go_id != 0 {
-                        children.push(build_node(
+                        children.extend(build_node(
                             child_go_id,
                             id_to_go,
                             id_to_trans,
@@ -1029,13 +1290,15 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
                             id_to_animator,
                             id_to_particle,
                             id_to_monos,
-                            project_path,
+                            id_to_diagnostics,
+                            id_to_raw,
+                            index,
                         ));
                     }
                 }
             }
         }
-        HierarchyNode {
+        Some(HierarchyNode {
             name,
             r#type,
             is_active,
@@ -1043,34 +1306,93 @@ fn build_hierarchy(prefab: &Prefab, project_path: &str) -> HierarchyNode {
             sprite_renderer,
             animator,
             particle_system,
-            animation_clips: None, // TODO: Implement animation clips parsing from controller
+            animation_clips,
             mono_behaviours,
+            raw_components,
             children,
-        }
+            world_matrix: [0.0; 16],
+            sprite_bounds: None,
+            bounds: None,
+            diagnostics,
+        })
     }
 
-    build_node(
-        root_go_id,
-        &id_to_go,
-        &id_to_trans,
-        &trans_id_to_go_id,
-        &go_id_to_trans_id,
-        &id_to_sprite,
-        &id_to_animator,
-        &id_to_particle,
-        &id_to_monos,
-        project_path,
-    )
+    root_trans
+        .iter()
+        .filter_map(|(_, trans, _)| {
+            build_node(
+                trans.m_game_object.file_id,
+                &id_to_go,
+                &id_to_trans,
+                &trans_id_to_go_id,
+                &go_id_to_trans_id,
+                &id_to_sprite,
+                &id_to_animator,
+                &id_to_particle,
+                &id_to_mono_views,
+                &id_to_diagnostics,
+                &id_to_raw,
+                index,
+            )
+        })
+        .map(|mut root| {
+            // Ma trận world và bounds tính một lần ở đây, consumer không phải tự nhân ma trận
+            apply_world_transforms(&mut root, &IDENTITY);
+            root
+        })
+        .collect()
 }
 
 #[tauri::command]
 pub async fn prefab_hierarchy(
     prefab_path: String,
     project_path: String,
-) -> Result<HierarchyNode, String> {
-    let prefab = read_prefab(prefab_path).await?;
-    let hierarchy = build_hierarchy(&prefab, &project_path);
-    Ok(hierarchy)
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<PrefabHierarchy, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let prefab = read_prefab(prefab_path, &index).await?;
+    Ok(PrefabHierarchy {
+        root: build_hierarchy(&prefab, &index)?,
+        document_diagnostics: prefab_diagnostics(&prefab),
+    })
+}
+
+/// Root node (serialized inline, same shape as before) plus per-document diagnostics
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefabHierarchy {
+    #[serde(flatten)]
+    pub root: HierarchyNode,
+    pub document_diagnostics: Vec<DocumentDiagnostic>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneHierarchy {
+    pub name: String,
+    pub path: String,
+    pub roots: Vec<HierarchyNode>,
+    pub document_diagnostics: Vec<DocumentDiagnostic>,
+}
+
+#[tauri::command]
+pub async fn scene_hierarchy(
+    scene_path: String,
+    project_path: String,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<SceneHierarchy, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let name = Path::new(&scene_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let prefab = read_prefab(scene_path.clone(), &index).await?;
+    Ok(SceneHierarchy {
+        name,
+        path: scene_path,
+        roots: build_forest(&prefab, &index),
+        document_diagnostics: prefab_diagnostics(&prefab),
+    })
 }
 
 // Animation structures
@@ -1144,7 +1466,7 @@ pub struct AnimationClip {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RotationCurve {
-    pub curve: AnimationCurve,
+    pub curve: QuaternionAnimationCurve,
     pub path: String,
 }
 
@@ -1197,7 +1519,7 @@ pub struct PPtrCurve {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PPtrKeyframe {
     pub time: Option<f64>,
-    pub value: MController,
+    pub value: FileReference,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1224,9 +1546,9 @@ pub struct Keyframe {
     pub in_slope: Option<f64>,
     #[serde(rename = "outSlope")]
     pub out_slope: Option<f64>,
-    #[serde(rename = "tangentMode")]
+    #[serde(rename = "tangentMode", default)]
     pub tangent_mode: i64,
-    #[serde(rename = "weightedMode")]
+    #[serde(rename = "weightedMode", default)]
     pub weighted_mode: i64,
     #[serde(rename = "inWeight")]
     pub in_weight: Option<f64>,
@@ -1244,9 +1566,9 @@ pub struct Vector3Keyframe {
     pub in_slope: Option<NullableVector3>,
     #[serde(rename = "outSlope")]
     pub out_slope: Option<NullableVector3>,
-    #[serde(rename = "tangentMode")]
+    #[serde(rename = "tangentMode", default)]
     pub tangent_mode: i64,
-    #[serde(rename = "weightedMode")]
+    #[serde(rename = "weightedMode", default)]
     pub weighted_mode: i64,
     #[serde(rename = "inWeight")]
     pub in_weight: Option<NullableVector3>,
@@ -1268,6 +1590,49 @@ pub struct Vector3AnimationCurve {
     pub m_rotation_order: i64,
 }
 
+// Slope/weight của quaternion keyframe; None khi Unity ghi Infinity (stepped)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NullableVector4 {
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub z: Option<f32>,
+    pub w: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuaternionKeyframe {
+    #[serde(rename = "serializedVersion", default)]
+    pub serialized_version: Option<i64>,
+    pub time: Option<f64>,
+    pub value: Vector4,
+    #[serde(rename = "inSlope")]
+    pub in_slope: Option<NullableVector4>,
+    #[serde(rename = "outSlope")]
+    pub out_slope: Option<NullableVector4>,
+    #[serde(rename = "tangentMode", default)]
+    pub tangent_mode: i64,
+    #[serde(rename = "weightedMode", default)]
+    pub weighted_mode: i64,
+    #[serde(rename = "inWeight")]
+    pub in_weight: Option<NullableVector4>,
+    #[serde(rename = "outWeight")]
+    pub out_weight: Option<NullableVector4>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuaternionAnimationCurve {
+    #[serde(rename = "serializedVersion", default)]
+    pub serialized_version: Option<i64>,
+    #[serde(rename = "m_Curve")]
+    pub m_curve: Vec<QuaternionKeyframe>,
+    #[serde(rename = "m_PreInfinity", default)]
+    pub m_pre_infinity: i64,
+    #[serde(rename = "m_PostInfinity", default)]
+    pub m_post_infinity: i64,
+    #[serde(rename = "m_RotationOrder", default)]
+    pub m_rotation_order: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AnimationBounds {
     #[serde(rename = "serializedVersion", default)]
@@ -1376,481 +1741,78 @@ pub struct AnimationEvent {
     pub message_options: i64,
 }
 
-// Particle System structures
+// Particle System structures: chỉ các field SimplifiedParticleSystem đọc, module thiếu
+// field thì lấy mặc định thay vì làm hỏng cả component
 #[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
 pub struct ParticleSystem {
-    #[serde(rename = "m_ObjectHideFlags")]
-    pub m_object_hide_flags: i64,
-    #[serde(rename = "m_CorrespondingSourceObject")]
-    pub m_corresponding_source_object: MAvatar,
-    #[serde(rename = "m_PrefabInstance")]
-    pub m_prefab_instance: MAvatar,
-    #[serde(rename = "m_PrefabAsset")]
-    pub m_prefab_asset: MAvatar,
     #[serde(rename = "m_GameObject")]
     pub m_game_object: MAvatar,
-    #[serde(rename = "m_Enabled")]
+    #[serde(rename = "m_Enabled", default = "enabled")]
     pub m_enabled: i64,
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
+    #[serde(default)]
     pub lengthInSec: f64,
+    #[serde(default)]
     pub simulationSpeed: f64,
-    pub stopAction: i64,
-    pub cullingMode: i64,
-    pub ringBufferMode: i64,
-    pub ringBufferLoopRange: MSize,
+    #[serde(default)]
     pub looping: i64,
+    #[serde(default)]
     pub prewarm: i64,
+    #[serde(default)]
     pub playOnAwake: i64,
-    pub useUnscaledTime: i64,
+    #[serde(default)]
     pub autoRandomSeed: i64,
-    pub useRigidbodyForVelocity: i64,
-    pub startDelay: MinMaxCurve,
+    /// Unity ghi các giá trị start* trong `InitialModule`
+    #[serde(rename = "InitialModule", default)]
+    pub initial_module: Option<InitialModule>,
+    /// Layout phẳng cũ (start* nằm ngay trong component)
+    #[serde(flatten)]
+    pub legacy_initial: InitialModule,
+    #[serde(alias = "ShapeModule", default)]
+    pub shape: ParticleShape,
+    #[serde(alias = "EmissionModule", default)]
+    pub emission: ParticleEmission,
+}
+
+impl ParticleSystem {
+    fn initial(&self) -> &InitialModule {
+        self.initial_module.as_ref().unwrap_or(&self.legacy_initial)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InitialModule {
+    #[serde(default)]
     pub startLifetime: MinMaxCurve,
+    #[serde(default)]
     pub startSpeed: MinMaxCurve,
-    pub startSize3D: i64,
+    #[serde(alias = "startSize", default)]
     pub startSizeX: MinMaxCurve,
-    pub startSizeY: MinMaxCurve,
-    pub startSizeZ: MinMaxCurve,
-    pub startRotation3D: i64,
-    pub startRotationX: MinMaxCurve,
-    pub startRotationY: MinMaxCurve,
-    pub startRotationZ: MinMaxCurve,
+    #[serde(default)]
     pub startColor: MinMaxGradient,
+    #[serde(default)]
     pub gravityModifier: MinMaxCurve,
-    pub shape: ParticleShape,
-    pub emission: ParticleEmission,
-    pub velocityOverLifetime: VelocityOverLifetime,
-    pub limitVelocityOverLifetime: LimitVelocityOverLifetime,
-    pub inheritVelocity: InheritVelocity,
-    pub forceOverLifetime: ForceOverLifetime,
-    pub colorOverLifetime: ColorOverLifetime,
-    pub colorBySpeed: ColorBySpeed,
-    pub sizeOverLifetime: SizeOverLifetime,
-    pub sizeBySpeed: SizeBySpeed,
-    pub rotationOverLifetime: RotationOverLifetime,
-    pub rotationBySpeed: RotationBySpeed,
-    pub externalForces: ExternalForces,
-    pub noise: ParticleNoise,
-    pub collision: ParticleCollision,
-    pub trigger: ParticleTrigger,
-    pub subEmitters: SubEmitters,
-    pub textureSheetAnimation: TextureSheetAnimation,
-    pub lights: ParticleLights,
-    pub trails: ParticleTrails,
-    pub customData: CustomData,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct MinMaxCurve {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub minMaxState: i64,
+    #[serde(default)]
     pub scalar: Option<f64>,
-    pub minScalar: Option<f64>,
-    pub maxCurve: AnimationCurve,
-    pub minCurve: AnimationCurve,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct MinMaxGradient {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub minMaxState: i64,
-    pub minColor: MColor,
+    #[serde(default)]
     pub maxColor: MColor,
-    pub maxGradient: Gradient,
-    pub minGradient: Gradient,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Gradient {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub key0: GradientColorKey,
-    pub key1: GradientColorKey,
-    pub key2: GradientColorKey,
-    pub key3: GradientColorKey,
-    pub key4: GradientColorKey,
-    pub key5: GradientColorKey,
-    pub key6: GradientColorKey,
-    pub key7: GradientColorKey,
-    pub ctime0: u16,
-    pub ctime1: u16,
-    pub ctime2: u16,
-    pub ctime3: u16,
-    pub ctime4: u16,
-    pub ctime5: u16,
-    pub ctime6: u16,
-    pub ctime7: u16,
-    pub atime0: u16,
-    pub atime1: u16,
-    pub atime2: u16,
-    pub atime3: u16,
-    pub atime4: u16,
-    pub atime5: u16,
-    pub atime6: u16,
-    pub atime7: u16,
-    #[serde(rename = "m_Mode")]
-    pub m_mode: i64,
-    #[serde(rename = "m_NumColorKeys")]
-    pub m_num_color_keys: i64,
-    #[serde(rename = "m_NumAlphaKeys")]
-    pub m_num_alpha_keys: i64,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct GradientColorKey {
-    pub color: MColor,
-    pub time: f64,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ParticleShape {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub shape_type: i64,
-    pub angle: Option<f64>,
-    pub length: Option<f64>,
-    pub boxThickness: MSize,
-    pub radiusThickness: Option<f64>,
-    pub donutRadius: Option<f64>,
-    #[serde(rename = "m_Position")]
-    pub m_position: MSize,
-    #[serde(rename = "m_Rotation")]
-    pub m_rotation: MSize,
-    #[serde(rename = "m_Scale")]
-    pub m_scale: MSize,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ParticleEmission {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
+    #[serde(default)]
     pub rateOverTime: MinMaxCurve,
-    pub rateOverDistance: MinMaxCurve,
-    #[serde(rename = "m_Bursts")]
-    pub m_bursts: Vec<ParticleBurst>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ParticleBurst {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub time: Option<f64>,
-    pub countCurve: MinMaxCurve,
-    pub cycleCount: i64,
-    pub repeatInterval: Option<f64>,
-    pub probability: Option<f64>,
-}
-
-// Additional particle system modules
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct VelocityOverLifetime {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub x: MinMaxCurve,
-    pub y: MinMaxCurve,
-    pub z: MinMaxCurve,
-    pub radial: MinMaxCurve,
-    pub speedModifier: MinMaxCurve,
-    pub space: i64,
-    pub orbitalX: MinMaxCurve,
-    pub orbitalY: MinMaxCurve,
-    pub orbitalZ: MinMaxCurve,
-    pub orbitalOffsetX: MinMaxCurve,
-    pub orbitalOffsetY: MinMaxCurve,
-    pub orbitalOffsetZ: MinMaxCurve,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct LimitVelocityOverLifetime {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub x: MinMaxCurve,
-    pub y: MinMaxCurve,
-    pub z: MinMaxCurve,
-    pub magnitude: MinMaxCurve,
-    pub separateAxes: i64,
-    pub space: i64,
-    pub drag: MinMaxCurve,
-    pub multiplyDragByParticleSize: i64,
-    pub multiplyDragByParticleVelocity: i64,
-    pub dampen: f64,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct InheritVelocity {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    #[serde(rename = "m_Mode")]
-    pub m_mode: i64,
-    #[serde(rename = "m_Curve")]
-    pub m_curve: MinMaxCurve,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ForceOverLifetime {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub x: MinMaxCurve,
-    pub y: MinMaxCurve,
-    pub z: MinMaxCurve,
-    pub space: i64,
-    pub randomizePerFrame: i64,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ColorOverLifetime {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub color: MinMaxGradient,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ColorBySpeed {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub color: MinMaxGradient,
-    pub range: MSize,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SizeOverLifetime {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub size: MinMaxCurve,
-    pub x: MinMaxCurve,
-    pub y: MinMaxCurve,
-    pub z: MinMaxCurve,
-    pub separateAxes: i64,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SizeBySpeed {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub size: MinMaxCurve,
-    pub x: MinMaxCurve,
-    pub y: MinMaxCurve,
-    pub z: MinMaxCurve,
-    pub separateAxes: i64,
-    pub range: MSize,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RotationOverLifetime {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub x: MinMaxCurve,
-    pub y: MinMaxCurve,
-    pub z: MinMaxCurve,
-    pub separateAxes: i64,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RotationBySpeed {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub x: MinMaxCurve,
-    pub y: MinMaxCurve,
-    pub z: MinMaxCurve,
-    pub separateAxes: i64,
-    pub range: MSize,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ExternalForces {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub multiplier: f64,
-    pub multiplierCurve: MinMaxCurve,
-    pub influenceFilter: i64,
-    pub influenceMask: LayerMask,
-    pub influenceList: Vec<MController>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct LayerMask {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    #[serde(rename = "m_Bits")]
-    pub m_bits: u32,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ParticleNoise {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub strength: MinMaxCurve,
-    pub strengthX: MinMaxCurve,
-    pub strengthY: MinMaxCurve,
-    pub strengthZ: MinMaxCurve,
-    pub separateAxes: i64,
-    pub frequency: Option<f64>,
-    pub damping: i64,
-    pub octaves: i64,
-    pub octaveMultiplier: Option<f64>,
-    pub octaveScale: Option<f64>,
-    pub quality: i64,
-    pub scrollSpeed: MinMaxCurve,
-    pub scrollSpeedX: MinMaxCurve,
-    pub scrollSpeedY: MinMaxCurve,
-    pub scrollSpeedZ: MinMaxCurve,
-    pub remapEnabled: i64,
-    pub remap: MinMaxCurve,
-    pub remapX: MinMaxCurve,
-    pub remapY: MinMaxCurve,
-    pub remapZ: MinMaxCurve,
-    pub positionAmount: MinMaxCurve,
-    pub rotationAmount: MinMaxCurve,
-    pub sizeAmount: MinMaxCurve,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ParticleCollision {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    #[serde(rename = "type")]
-    pub collision_type: i64,
-    #[serde(rename = "mode")]
-    pub collision_mode: i64,
-    pub dampen: MinMaxCurve,
-    pub bounce: MinMaxCurve,
-    pub lifetimeLoss: MinMaxCurve,
-    pub minKillSpeed: Option<f64>,
-    pub maxKillSpeed: Option<f64>,
-    pub radiusScale: Option<f64>,
-    pub collidesWith: LayerMask,
-    pub maxCollisionShapes: i64,
-    pub quality: i64,
-    pub voxelSize: Option<f64>,
-    pub collisionMessages: i64,
-    pub collidesWithDynamic: i64,
-    pub interiorCollisions: i64,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ParticleTrigger {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub collidesWith: LayerMask,
-    pub inside: i64,
-    pub outside: i64,
-    pub enter: i64,
-    pub exit: i64,
-    pub radiusScale: Option<f64>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SubEmitters {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    #[serde(rename = "m_SubEmitters")]
-    pub m_sub_emitters: Vec<SubEmitter>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SubEmitter {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub emitter: MController,
-    #[serde(rename = "type")]
-    pub sub_emitter_type: i64,
-    pub properties: i64,
-    pub emitProbability: Option<f64>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct TextureSheetAnimation {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    #[serde(rename = "mode")]
-    pub animation_mode: i64,
-    pub timeMode: i64,
-    pub fps: Option<f64>,
-    pub numTilesX: i64,
-    pub numTilesY: i64,
-    pub animation: i64,
-    pub rowMode: i64,
-    pub cycles: Option<f64>,
-    pub uvChannelMask: i64,
-    pub randomRow: i64,
-    pub sprites: Vec<MController>,
-    pub flipU: Option<f64>,
-    pub flipV: Option<f64>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ParticleLights {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    pub ratio: f64,
-    pub randomDistribution: i64,
-    pub color: i64,
-    pub useRandomColorRange: i64,
-    pub colorRangeHueMin: Option<f64>,
-    pub colorRangeHueMax: Option<f64>,
-    pub colorRangeSaturationMin: Option<f64>,
-    pub colorRangeSaturationMax: Option<f64>,
-    pub colorRangeValueMin: Option<f64>,
-    pub colorRangeValueMax: Option<f64>,
-    pub intensity: MinMaxCurve,
-    pub range: MinMaxCurve,
-    pub rangeCurve: MinMaxCurve,
-    pub sizeAffectsRange: i64,
-    pub alphaAffectsIntensity: i64,
-    pub includeInBounding: i64,
-    pub light: MController,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ParticleTrails {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
-    #[serde(rename = "mode")]
-    pub trail_mode: i64,
-    pub ratio: Option<f64>,
-    pub lifetime: MinMaxCurve,
-    pub minVertexDistance: Option<f64>,
-    pub textureMode: i64,
-    pub worldSpace: i64,
-    pub dieWithParticles: i64,
-    pub sizeAffectsWidth: i64,
-    pub sizeAffectsLifetime: i64,
-    pub inheritParticleColor: i64,
-    pub colorOverLifetime: MinMaxGradient,
-    pub widthOverTrail: MinMaxCurve,
-    pub colorOverTrail: MinMaxGradient,
-    pub generateLightingData: i64,
-    pub shadowBias: Option<f64>,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct CustomData {
-    #[serde(rename = "serializedVersion", default)]
-    pub serialized_version: Option<i64>,
-    pub enabled: i64,
 }
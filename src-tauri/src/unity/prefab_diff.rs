@@ -0,0 +1,440 @@
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use crate::unity::prefab::{build_forest, read_prefab, HierarchyNode};
+use crate::unity::texture::TextureImporter;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use tauri::State;
+
+// Sai số khi so sánh float (YAML export hay lệch ở chữ số cuối)
+const EPSILON: f64 = 1e-5;
+
+// Field thay đổi theo fileID/encode, không có ý nghĩa khi so sánh 2 version
+const IGNORED_FIELDS: &[&str] = &["fatherId", "childrenIds", "spriteBase64"];
+
+/// One value that differs, `path` is relative to the component (`localPosition.x`)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub path: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpriteChange {
+    /// `None` on the side that has no SpriteRenderer
+    pub old_guid: Option<String>,
+    pub new_guid: Option<String>,
+    /// fileID inside the texture (which sprite of a sheet)
+    pub old_file_id: Option<i64>,
+    pub new_file_id: Option<i64>,
+    /// Sprite name in each version (sheet entry name, or the asset name)
+    pub old_name: Option<String>,
+    pub new_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MonoBehaviourDiff {
+    pub script_guid: String,
+    /// `Namespace.ClassName` when the script resolves
+    pub class_name: Option<String>,
+    pub kind: ChangeKind,
+    pub fields: Vec<FieldChange>,
+}
+
+/// Changes of one GameObject that exists in both versions
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeDiff {
+    pub path: String,
+    pub is_active: Option<FieldChange>,
+    pub transform: Vec<FieldChange>,
+    pub sprite: Option<SpriteChange>,
+    pub sprite_renderer: Vec<FieldChange>,
+    pub animator: Vec<FieldChange>,
+    pub particle_system: Vec<FieldChange>,
+    pub mono_behaviours: Vec<MonoBehaviourDiff>,
+}
+
+impl NodeDiff {
+    fn is_empty(&self) -> bool {
+        self.is_active.is_none()
+            && self.transform.is_empty()
+            && self.sprite.is_none()
+            && self.sprite_renderer.is_empty()
+            && self.animator.is_empty()
+            && self.particle_system.is_empty()
+            && self.mono_behaviours.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefabDiff {
+    /// GameObject paths (`Hero/Body/Arm`) only in the new version
+    pub added: Vec<String>,
+    /// GameObject paths only in the old version
+    pub removed: Vec<String>,
+    pub changed: Vec<NodeDiff>,
+}
+
+// Đường dẫn theo tên GameObject; anh em trùng tên được đánh số `Name[1]`
+fn collect_paths<'a>(
+    nodes: &'a [HierarchyNode],
+    prefix: &str,
+    out: &mut Vec<(String, &'a HierarchyNode)>,
+) {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for node in nodes {
+        let count = seen.entry(node.name.as_str()).or_default();
+        let name = if *count == 0 {
+            node.name.clone()
+        } else {
+            format!("{}[{}]", node.name, count)
+        };
+        *count += 1;
+        let path = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+        out.push((path.clone(), node));
+        collect_paths(&node.children, &path, out);
+    }
+}
+
+fn node_paths(roots: &[HierarchyNode]) -> Vec<(String, &HierarchyNode)> {
+    let mut paths = Vec::new();
+    match roots {
+        // Prefab đổi tên file thì root cũng đổi tên: dùng đường dẫn không có root
+        [root] => {
+            paths.push((String::new(), root));
+            collect_paths(&root.children, "", &mut paths);
+        }
+        _ => collect_paths(roots, "", &mut paths),
+    }
+    paths
+}
+
+// Reference trong field của MonoBehaviour được so sánh theo target, không theo fileID
+fn normalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            if map.contains_key("fileID") {
+                if map.get("fileID").and_then(Value::as_i64) == Some(0) {
+                    return Value::Null;
+                }
+                if let Some(target) = map.get("target") {
+                    let label = target
+                        .get("path")
+                        .filter(|p| !p.is_null())
+                        .cloned()
+                        .unwrap_or_else(|| {
+                            let name = target.get("name").and_then(Value::as_str);
+                            let type_name = target.get("typeName").and_then(Value::as_str);
+                            Value::String(format!(
+                                "{} ({})",
+                                name.unwrap_or_default(),
+                                type_name.unwrap_or_default()
+                            ))
+                        });
+                    return label;
+                }
+            }
+            Value::Object(
+                map.iter()
+                    .filter(|(k, _)| !IGNORED_FIELDS.contains(&k.as_str()))
+                    .map(|(k, v)| (k.clone(), normalize(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(normalize).collect()),
+        other => other.clone(),
+    }
+}
+
+fn same_value(old: &Value, new: &Value) -> bool {
+    match (old, new) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_f64(), b.as_f64()) {
+            (Some(a), Some(b)) => (a - b).abs() <= EPSILON,
+            _ => a == b,
+        },
+        _ => old == new,
+    }
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn diff_values(prefix: &str, old: &Value, new: &Value, changes: &mut Vec<FieldChange>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let old = a.get(key).unwrap_or(&Value::Null);
+                let new = b.get(key).unwrap_or(&Value::Null);
+                diff_values(&join_path(prefix, key), old, new, changes);
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (i, (old, new)) in a.iter().zip(b).enumerate() {
+                diff_values(&format!("{}[{}]", prefix, i), old, new, changes);
+            }
+        }
+        _ if same_value(old, new) => {}
+        _ => changes.push(FieldChange {
+            path: prefix.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+fn diff_component<T: Serialize>(old: &T, new: &T) -> Vec<FieldChange> {
+    let old = normalize(&serde_json::to_value(old).unwrap_or_default());
+    let new = normalize(&serde_json::to_value(new).unwrap_or_default());
+    let mut changes = Vec::new();
+    diff_values("", &old, &new, &mut changes);
+    changes
+}
+
+// Component chỉ có ở một bên được báo là một thay đổi, path là tên component
+fn diff_optional_component<T: Serialize>(
+    name: &str,
+    old: &Option<T>,
+    new: &Option<T>,
+) -> Vec<FieldChange> {
+    let to_value = |c: &T| normalize(&serde_json::to_value(c).unwrap_or_default());
+    match (old, new) {
+        (Some(old), Some(new)) => diff_component(old, new),
+        (None, None) => Vec::new(),
+        _ => vec![FieldChange {
+            path: name.to_string(),
+            old: old.as_ref().map(to_value).unwrap_or_default(),
+            new: new.as_ref().map(to_value).unwrap_or_default(),
+        }],
+    }
+}
+
+// Sprite trong sheet lấy tên từ TextureImporter, sprite đơn dùng tên file
+fn sprite_name(guid: &str, file_id: i64, index: &GuidIndex) -> Option<String> {
+    index
+        .resolve_str(guid)
+        .and_then(|path| TextureImporter::read(&path))
+        .and_then(|importer| importer.sheet_sprite(file_id).map(|s| s.name.clone()))
+        .or_else(|| asset_name(guid, index))
+}
+
+fn asset_name(guid: &str, index: &GuidIndex) -> Option<String> {
+    let entry = index.entry(guid)?;
+    Path::new(&entry.path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+}
+
+fn script_key(mono: &Value) -> String {
+    mono.get("scriptGuid")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn script_class(mono: &Value) -> Option<String> {
+    let script = mono.get("script")?;
+    let class_name = script.get("className")?.as_str()?;
+    Some(match script.get("namespace").and_then(Value::as_str) {
+        Some(ns) if !ns.is_empty() => format!("{}.{}", ns, class_name),
+        _ => class_name.to_string(),
+    })
+}
+
+// Ghép MonoBehaviour theo script GUID (và thứ tự nếu một GameObject có nhiều cái cùng script)
+fn diff_mono_behaviours(old: &HierarchyNode, new: &HierarchyNode) -> Vec<MonoBehaviourDiff> {
+    let to_values = |node: &HierarchyNode| -> Vec<Value> {
+        node.mono_behaviours
+            .iter()
+            .map(|m| serde_json::to_value(m).unwrap_or_default())
+            .collect()
+    };
+    let old_monos = to_values(old);
+    let mut new_monos: Vec<Option<Value>> = to_values(new).into_iter().map(Some).collect();
+
+    let mut diffs = Vec::new();
+    for old_mono in &old_monos {
+        let key = script_key(old_mono);
+        let matched = new_monos
+            .iter_mut()
+            .find(|m| m.as_ref().is_some_and(|m| script_key(m) == key))
+            .and_then(Option::take);
+        let Some(new_mono) = matched else {
+            diffs.push(MonoBehaviourDiff {
+                script_guid: key,
+                class_name: script_class(old_mono),
+                kind: ChangeKind::Removed,
+                fields: Vec::new(),
+            });
+            continue;
+        };
+        let fields = diff_component(
+            &Value::Object(mono_state(old_mono)),
+            &Value::Object(mono_state(&new_mono)),
+        );
+        if !fields.is_empty() {
+            diffs.push(MonoBehaviourDiff {
+                script_guid: key,
+                class_name: script_class(&new_mono).or_else(|| script_class(old_mono)),
+                kind: ChangeKind::Changed,
+                fields,
+            });
+        }
+    }
+    for new_mono in new_monos.into_iter().flatten() {
+        diffs.push(MonoBehaviourDiff {
+            script_guid: script_key(&new_mono),
+            class_name: script_class(&new_mono),
+            kind: ChangeKind::Added,
+            fields: Vec::new(),
+        });
+    }
+    diffs
+}
+
+// Chỉ so sánh trạng thái enabled và field của script
+fn mono_state(mono: &Value) -> Map<String, Value> {
+    let mut state = mono
+        .get("fields")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    if let Some(enabled) = mono.get("enabled") {
+        state.insert("m_Enabled".to_string(), enabled.clone());
+    }
+    state
+}
+
+fn diff_node(
+    path: &str,
+    old: &HierarchyNode,
+    new: &HierarchyNode,
+    old_index: &GuidIndex,
+    new_index: &GuidIndex,
+) -> NodeDiff {
+    let mut diff = NodeDiff {
+        path: path.to_string(),
+        ..Default::default()
+    };
+    if old.is_active != new.is_active {
+        diff.is_active = Some(FieldChange {
+            path: "isActive".to_string(),
+            old: Value::from(old.is_active),
+            new: Value::from(new.is_active),
+        });
+    }
+    diff.transform = diff_component(&old.transform, &new.transform);
+    diff.sprite_renderer =
+        diff_optional_component("spriteRenderer", &old.sprite_renderer, &new.sprite_renderer);
+    // Sprite đổi được báo riêng kèm tên sprite; cùng sheet khác fileID cũng là đổi sprite
+    diff.sprite_renderer
+        .retain(|c| c.path != "spriteGuid" && c.path != "spriteFileId");
+    let old_sprite = old
+        .sprite_renderer
+        .as_ref()
+        .map(|s| (s.sprite_guid.as_str(), s.sprite_file_id));
+    let new_sprite = new
+        .sprite_renderer
+        .as_ref()
+        .map(|s| (s.sprite_guid.as_str(), s.sprite_file_id));
+    if old_sprite != new_sprite {
+        let name = |sprite: Option<(&str, i64)>, index: &GuidIndex| {
+            sprite.and_then(|(guid, file_id)| sprite_name(guid, file_id, index))
+        };
+        diff.sprite = Some(SpriteChange {
+            old_guid: old_sprite.map(|(guid, _)| guid.to_string()),
+            new_guid: new_sprite.map(|(guid, _)| guid.to_string()),
+            old_file_id: old_sprite.map(|(_, file_id)| file_id),
+            new_file_id: new_sprite.map(|(_, file_id)| file_id),
+            old_name: name(old_sprite, old_index),
+            new_name: name(new_sprite, new_index),
+        });
+    }
+    diff.animator = diff_optional_component("animator", &old.animator, &new.animator);
+    diff.particle_system =
+        diff_optional_component("particleSystem", &old.particle_system, &new.particle_system);
+    diff.mono_behaviours = diff_mono_behaviours(old, new);
+    diff
+}
+
+/// Align two hierarchies by GameObject path and report what changed
+pub fn diff_hierarchies(
+    old_roots: &[HierarchyNode],
+    new_roots: &[HierarchyNode],
+    old_index: &GuidIndex,
+    new_index: &GuidIndex,
+) -> PrefabDiff {
+    let old_paths = node_paths(old_roots);
+    let new_paths = node_paths(new_roots);
+    let old_by_path: HashMap<&str, &HierarchyNode> =
+        old_paths.iter().map(|(p, n)| (p.as_str(), *n)).collect();
+    let new_by_path: HashMap<&str, &HierarchyNode> =
+        new_paths.iter().map(|(p, n)| (p.as_str(), *n)).collect();
+
+    let mut result = PrefabDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+    for (path, new_node) in &new_paths {
+        match old_by_path.get(path.as_str()) {
+            Some(old_node) => {
+                let diff = diff_node(path, old_node, new_node, old_index, new_index);
+                if !diff.is_empty() {
+                    result.changed.push(diff);
+                }
+            }
+            None => result.added.push(path.clone()),
+        }
+    }
+    for (path, _) in &old_paths {
+        if !new_by_path.contains_key(path.as_str()) {
+            result.removed.push(path.clone());
+        }
+    }
+    result
+}
+
+#[tauri::command]
+pub async fn diff_prefabs(
+    old_project_path: String,
+    old_prefab_path: String,
+    new_project_path: String,
+    new_prefab_path: String,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<PrefabDiff, String> {
+    let old_index = project_index(&guid_index, &old_project_path).await?;
+    let new_index = project_index(&guid_index, &new_project_path).await?;
+    let old_prefab = read_prefab(old_prefab_path, &old_index).await?;
+    let new_prefab = read_prefab(new_prefab_path, &new_index).await?;
+    Ok(diff_hierarchies(
+        &build_forest(&old_prefab, &old_index),
+        &build_forest(&new_prefab, &new_index),
+        &old_index,
+        &new_index,
+    ))
+}
@@ -0,0 +1,428 @@
+use crate::unity::document::{
+    parse_unity_documents, UnityDocument, CLASS_PREFAB_INSTANCE, CLASS_RECT_TRANSFORM,
+    CLASS_TRANSFORM,
+};
+use crate::unity::error::UnityParseError;
+use crate::unity::guid_index::GuidIndex;
+use crate::unity::prefab::FileReference;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+// Giới hạn số tầng prefab lồng nhau
+const MAX_NESTING_DEPTH: usize = 16;
+
+#[derive(Deserialize, Debug, Clone)]
+struct PropertyModification {
+    target: FileReference,
+    #[serde(rename = "propertyPath")]
+    property_path: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(rename = "objectReference", default)]
+    object_reference: Option<FileReference>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct PrefabModification {
+    #[serde(rename = "m_TransformParent", default)]
+    m_transform_parent: Option<FileReference>,
+    #[serde(rename = "m_Modifications", default)]
+    m_modifications: Vec<PropertyModification>,
+    #[serde(rename = "m_RemovedComponents", default)]
+    m_removed_components: Vec<FileReference>,
+    #[serde(rename = "m_RemovedGameObjects", default)]
+    m_removed_game_objects: Vec<FileReference>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct PrefabInstanceDoc {
+    #[serde(rename = "m_Modification")]
+    m_modification: PrefabModification,
+    #[serde(rename = "m_SourcePrefab")]
+    m_source_prefab: FileReference,
+}
+
+/// File ID Unity gives to an object of a source prefab once instantiated in another file
+pub(crate) fn instance_file_id(instance_id: i64, source_file_id: i64) -> i64 {
+    (instance_id ^ source_file_id) & i64::MAX
+}
+
+fn reference_file_id(value: &Value, key: &str) -> i64 {
+    value
+        .get(key)
+        .and_then(|r| r.get("fileID"))
+        .and_then(Value::as_i64)
+        .unwrap_or(0)
+}
+
+// Trạng thái của một lần load_merged_documents
+struct MergeContext<'a> {
+    index: &'a GuidIndex,
+    /// GUID các prefab đang được merge, để chặn vòng lặp
+    stack: Vec<String>,
+    // Prefab nguồn đã merge theo GUID: nhiều instance của cùng một part chỉ parse một lần
+    sources: HashMap<String, Vec<UnityDocument>>,
+}
+
+/// Read a prefab/scene file and inline every PrefabInstance it contains
+pub(crate) fn load_merged_documents(
+    path: &Path,
+    index: &GuidIndex,
+) -> Result<Vec<UnityDocument>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read prefab file: {}", e))?;
+    let mut context = MergeContext {
+        index,
+        stack: index.guid_for_path(path).cloned().into_iter().collect(),
+        sources: HashMap::new(),
+    };
+    Ok(merge_prefab_instances(
+        parse_unity_documents(&content),
+        &mut context,
+    ))
+}
+
+fn merge_prefab_instances(
+    documents: Vec<UnityDocument>,
+    context: &mut MergeContext,
+) -> Vec<UnityDocument> {
+    let (instances, others): (Vec<UnityDocument>, Vec<UnityDocument>) = documents
+        .into_iter()
+        .partition(|d| d.class_id == CLASS_PREFAB_INSTANCE);
+
+    // Document stripped chỉ là placeholder, object thật đến từ prefab nguồn
+    let mut merged: Vec<UnityDocument> = others.into_iter().filter(|d| !d.stripped).collect();
+    for instance in &instances {
+        match instance_documents(instance, context) {
+            Ok(documents) => merged.extend(documents),
+            Err(error) => merged.push(UnityDocument::failed(
+                CLASS_PREFAB_INSTANCE,
+                instance.file_id,
+                error,
+            )),
+        }
+    }
+    if !instances.is_empty() {
+        link_children(&mut merged);
+    }
+    merged
+}
+
+// Documents của prefab nguồn (đã merge các instance bên trong), đọc mỗi GUID một lần
+fn source_documents(
+    source_guid: &str,
+    context: &mut MergeContext,
+) -> Result<Vec<UnityDocument>, UnityParseError> {
+    if let Some(documents) = context.sources.get(source_guid) {
+        return Ok(documents.clone());
+    }
+    let source_path =
+        context
+            .index
+            .resolve(source_guid)
+            .ok_or_else(|| UnityParseError::MissingGuid {
+                guid: source_guid.to_string(),
+            })?;
+    let content =
+        fs::read_to_string(&source_path).map_err(|e| UnityParseError::UnreadablePrefab {
+            guid: source_guid.to_string(),
+            path: source_path.display().to_string(),
+            message: e.to_string(),
+        })?;
+
+    context.stack.push(source_guid.to_string());
+    let documents = merge_prefab_instances(parse_unity_documents(&content), context);
+    context.stack.pop();
+    context
+        .sources
+        .insert(source_guid.to_string(), documents.clone());
+    Ok(documents)
+}
+
+// GameObject bị xóa kéo theo component của nó và cả nhánh Transform con
+fn removed_objects(
+    documents: &[UnityDocument],
+    removed_game_objects: &[FileReference],
+) -> HashSet<i64> {
+    let is_transform =
+        |d: &UnityDocument| matches!(d.class_id, CLASS_TRANSFORM | CLASS_RECT_TRANSFORM);
+    let mut children: HashMap<i64, Vec<&UnityDocument>> = HashMap::new();
+    for document in documents.iter().filter(|d| is_transform(d)) {
+        if let Some(father) = document.reference("m_Father").filter(|f| *f != 0) {
+            children.entry(father).or_default().push(document);
+        }
+    }
+
+    let mut game_objects: HashSet<i64> = removed_game_objects.iter().map(|r| r.file_id).collect();
+    let mut queue: Vec<i64> = documents
+        .iter()
+        .filter(|d| is_transform(d))
+        .filter(|d| game_objects.contains(&d.reference("m_GameObject").unwrap_or(0)))
+        .map(|d| d.file_id)
+        .collect();
+    while let Some(transform) = queue.pop() {
+        for child in children.get(&transform).into_iter().flatten() {
+            game_objects.extend(child.reference("m_GameObject"));
+            queue.push(child.file_id);
+        }
+    }
+
+    let mut removed = game_objects.clone();
+    for document in documents {
+        if game_objects.contains(&document.reference("m_GameObject").unwrap_or(0)) {
+            removed.insert(document.file_id);
+        }
+    }
+    removed
+}
+
+// Các object của prefab nguồn, đã đổi file ID và áp dụng m_Modifications
+fn instance_documents(
+    instance: &UnityDocument,
+    context: &mut MergeContext,
+) -> Result<Vec<UnityDocument>, UnityParseError> {
+    let doc: PrefabInstanceDoc = instance.view()?;
+    let Some(source_guid) = doc.m_source_prefab.guid.clone() else {
+        return Ok(Vec::new());
+    };
+    if context.stack.contains(&source_guid) || context.stack.len() >= MAX_NESTING_DEPTH {
+        return Err(UnityParseError::RecursivePrefab {
+            guid: source_guid,
+            depth: context.stack.len(),
+        });
+    }
+    let source_documents = source_documents(&source_guid, context)?;
+
+    let modification = doc.m_modification;
+    let mut removed = removed_objects(&source_documents, &modification.m_removed_game_objects);
+    removed.extend(modification.m_removed_components.iter().map(|r| r.file_id));
+
+    let id_map: HashMap<i64, i64> = source_documents
+        .iter()
+        .map(|d| (d.file_id, instance_file_id(instance.file_id, d.file_id)))
+        .collect();
+    let mut modifications: HashMap<i64, Vec<&PropertyModification>> = HashMap::new();
+    for m in &modification.m_modifications {
+        modifications.entry(m.target.file_id).or_default().push(m);
+    }
+    let transform_parent = modification
+        .m_transform_parent
+        .as_ref()
+        .map(|r| r.file_id)
+        .unwrap_or(0);
+
+    let mut documents = Vec::new();
+    for mut document in source_documents {
+        if removed.contains(&document.file_id) {
+            continue;
+        }
+        let source_file_id = document.file_id;
+        document.file_id = id_map[&source_file_id];
+        remap_references(&mut document.body, &id_map);
+        if document.error.is_some() {
+            documents.push(document);
+            continue;
+        }
+
+        let inner = &mut document.body;
+        // Root của prefab nguồn được gắn vào m_TransformParent
+        let is_transform = matches!(document.class_id, CLASS_TRANSFORM | CLASS_RECT_TRANSFORM);
+        if is_transform && reference_file_id(inner, "m_Father") == 0 {
+            set_property(
+                inner,
+                "m_Father",
+                serde_json::json!({ "fileID": transform_parent }),
+            );
+        }
+        for m in modifications.get(&source_file_id).into_iter().flatten() {
+            let new_value = modification_value(get_property(inner, &m.property_path), m);
+            set_property(inner, &m.property_path, new_value);
+        }
+        documents.push(document);
+    }
+    Ok(documents)
+}
+
+// Đổi các tham chiếu nội bộ {fileID: X} (không có guid) sang ID mới
+fn remap_references(value: &mut Value, id_map: &HashMap<i64, i64>) {
+    match value {
+        Value::Object(map) => {
+            if !map.contains_key("guid") {
+                if let Some(new_id) = map
+                    .get("fileID")
+                    .and_then(Value::as_i64)
+                    .and_then(|id| id_map.get(&id))
+                {
+                    map.insert("fileID".to_string(), Value::from(*new_id));
+                }
+            }
+            for child in map.values_mut() {
+                remap_references(child, id_map);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                remap_references(item, id_map);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn array_index(segment: &str) -> Option<usize> {
+    segment
+        .strip_prefix("data[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+// propertyPath dạng "m_LocalPosition.x" hoặc "m_Children.Array.data[2]"
+fn get_property<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = value;
+    for segment in path.split('.') {
+        current = match segment {
+            "Array" => current,
+            "size" if current.is_array() => return None,
+            _ => match array_index(segment) {
+                Some(i) => current.get(i)?,
+                None => current.get(segment)?,
+            },
+        };
+    }
+    Some(current)
+}
+
+/// Apply one `propertyPath` override, creating missing objects and growing arrays
+fn set_property(value: &mut Value, path: &str, new_value: Value) {
+    let segments: Vec<&str> = path.split('.').filter(|s| *s != "Array").collect();
+    let mut current = value;
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i == segments.len() - 1;
+
+        if *segment == "size" && current.is_array() {
+            if let (Some(items), Some(size)) = (current.as_array_mut(), new_value.as_u64()) {
+                resize_array(items, size as usize);
+            }
+            return;
+        }
+
+        if let Some(index) = array_index(segment) {
+            let Some(items) = current.as_array_mut() else {
+                return;
+            };
+            if items.len() <= index {
+                resize_array(items, index + 1);
+            }
+            if last {
+                items[index] = new_value;
+                return;
+            }
+            current = &mut items[index];
+            continue;
+        }
+
+        if !current.is_object() {
+            *current = Value::Object(serde_json::Map::new());
+        }
+        let Some(map) = current.as_object_mut() else {
+            return;
+        };
+        if last {
+            map.insert(segment.to_string(), new_value);
+            return;
+        }
+        let next_is_array = segments
+            .get(i + 1)
+            .is_some_and(|s| *s == "size" || array_index(s).is_some());
+        current = map.entry(segment.to_string()).or_insert_with(|| {
+            if next_is_array {
+                Value::Array(Vec::new())
+            } else {
+                Value::Object(serde_json::Map::new())
+            }
+        });
+    }
+}
+
+// Phần tử mới: tham chiếu rỗng nếu mảng chứa tham chiếu, còn lại copy phần tử cuối
+fn resize_array(items: &mut Vec<Value>, size: usize) {
+    let filler = match items.last() {
+        Some(Value::Object(map)) if map.contains_key("fileID") => {
+            serde_json::json!({ "fileID": 0 })
+        }
+        Some(last) => last.clone(),
+        None => Value::Null,
+    };
+    items.resize(size, filler);
+}
+
+// Giá trị trong m_Modifications luôn là scalar dạng text, ép lại kiểu theo giá trị gốc
+fn modification_value(existing: Option<&Value>, m: &PropertyModification) -> Value {
+    let reference = m
+        .object_reference
+        .as_ref()
+        .filter(|r| r.file_id != 0 || r.guid.is_some());
+    let existing_is_reference = existing
+        .and_then(Value::as_object)
+        .is_some_and(|o| o.contains_key("fileID"));
+    if existing_is_reference || (m.value.is_null() && reference.is_some()) {
+        return match reference {
+            Some(r) => serde_json::to_value(r).unwrap_or(Value::Null),
+            None => serde_json::json!({ "fileID": 0 }),
+        };
+    }
+
+    match (existing, &m.value) {
+        (Some(Value::Number(_)), Value::String(s)) => s
+            .trim()
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| s.trim().parse::<f64>().map(Value::from))
+            .unwrap_or_else(|_| m.value.clone()),
+        (Some(Value::String(_)), Value::Number(n)) => Value::String(n.to_string()),
+        (Some(Value::String(_)), Value::Null) => Value::String(String::new()),
+        (Some(Value::Number(_)), Value::Null) => Value::from(0),
+        _ => m.value.clone(),
+    }
+}
+
+// Đảm bảo m_Children của transform cha chứa các transform con được thêm vào trong variant
+fn link_children(documents: &mut [UnityDocument]) {
+    let is_transform =
+        |d: &UnityDocument| matches!(d.class_id, CLASS_TRANSFORM | CLASS_RECT_TRANSFORM);
+    let links: Vec<(i64, i64)> = documents
+        .iter()
+        .filter(|d| is_transform(d))
+        .filter_map(|d| Some((d.reference("m_Father")?, d.file_id)))
+        .filter(|(father, _)| *father != 0)
+        .collect();
+
+    let positions: HashMap<i64, usize> = documents
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| is_transform(d))
+        .map(|(i, d)| (d.file_id, i))
+        .collect();
+    for (father, child) in links {
+        let Some(&position) = positions.get(&father) else {
+            continue;
+        };
+        let Some(children) = documents[position]
+            .body
+            .get_mut("m_Children")
+            .and_then(Value::as_array_mut)
+        else {
+            continue;
+        };
+        let exists = children
+            .iter()
+            .any(|c| c.get("fileID").and_then(Value::as_i64) == Some(child));
+        if !exists {
+            children.push(serde_json::json!({ "fileID": child }));
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::unity::guid_index::GuidIndex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Object inside the same file that a `{fileID: ...}` can point to
+#[derive(Debug, Clone)]
+pub struct LocalObject {
+    pub type_name: String,
+    /// Name of the GameObject (the object itself, or the one owning the component)
+    pub name: Option<String>,
+}
+
+/// Where a `{fileID, guid}` reference points to
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectReference {
+    pub file_id: i64,
+    pub guid: Option<String>,
+    /// Unity type of the local object (`GameObject`, `Transform`...), unknown for assets
+    pub type_name: Option<String>,
+    /// GameObject name, or the asset file name for references into another file
+    pub name: Option<String>,
+    /// Project-relative asset path for references into another file
+    pub path: Option<String>,
+    /// The fileID/GUID does not exist in this file or in the project
+    pub missing: bool,
+}
+
+// `{fileID: 0}` là null reference
+fn reference_parts(map: &Map<String, Value>) -> Option<(i64, Option<&str>)> {
+    let file_id = map.get("fileID")?.as_i64()?;
+    if map
+        .keys()
+        .any(|k| !matches!(k.as_str(), "fileID" | "guid" | "type"))
+    {
+        return None;
+    }
+    let guid = map
+        .get("guid")
+        .and_then(Value::as_str)
+        .filter(|g| !g.is_empty());
+    Some((file_id, guid))
+}
+
+fn resolve_reference(
+    file_id: i64,
+    guid: Option<&str>,
+    locals: &HashMap<i64, LocalObject>,
+    index: &GuidIndex,
+) -> ObjectReference {
+    let mut reference = ObjectReference {
+        file_id,
+        guid: guid.map(str::to_string),
+        type_name: None,
+        name: None,
+        path: None,
+        missing: false,
+    };
+    match guid {
+        Some(guid) => match index.entry(guid) {
+            Some(entry) => {
+                reference.name = Path::new(&entry.path)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string());
+                reference.path = Some(entry.path.clone());
+            }
+            None => reference.missing = true,
+        },
+        None => match locals.get(&file_id) {
+            Some(local) => {
+                reference.type_name = Some(local.type_name.clone());
+                reference.name = local.name.clone();
+            }
+            None => reference.missing = true,
+        },
+    }
+    reference
+}
+
+/// Copy of a serialized field tree where every non-null `{fileID, guid, type}`
+/// gets a `target` describing the object it points to
+pub fn resolve_references(
+    value: &Value,
+    locals: &HashMap<i64, LocalObject>,
+    index: &GuidIndex,
+) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some((file_id, guid)) = reference_parts(map) {
+                let mut map = map.clone();
+                if file_id != 0 {
+                    let target = resolve_reference(file_id, guid, locals, index);
+                    map.insert(
+                        "target".to_string(),
+                        serde_json::to_value(target).unwrap_or_default(),
+                    );
+                }
+                return Value::Object(map);
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), resolve_references(v, locals, index)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|v| resolve_references(v, locals, index))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
@@ -0,0 +1,827 @@
+use crate::unity::animation::load_animation_clip;
+use crate::unity::animator::load_animator_controller;
+use crate::unity::curve::{frame_times, sample_clip, TransformPose};
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState};
+use crate::unity::prefab::{
+    build_hierarchy, load_sprite, read_prefab, HierarchyNode, LoadedSprite,
+};
+use crate::unity::transform::{euler_to_quaternion, multiply, trs, Affine, IDENTITY};
+use base64::{engine::general_purpose, Engine as _};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::State;
+
+// Giới hạn kích thước canvas để tránh cấp phát quá lớn khi scale sai
+const MAX_CANVAS_SIZE: u32 = 8192;
+const DEFAULT_PIXELS_PER_UNIT: f64 = 100.0;
+
+/// 2D affine `x' = a*x + b*y + c`, `y' = d*x + e*y + f`
+type Affine2D = [f64; 6];
+
+fn invert_2d(m: &Affine2D) -> Option<Affine2D> {
+    let [a, b, c, d, e, f] = *m;
+    let det = a * e - b * d;
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    Some([
+        e / det,
+        -b / det,
+        (b * f - c * e) / det,
+        -d / det,
+        a / det,
+        (c * d - a * f) / det,
+    ])
+}
+
+fn apply_2d(m: &Affine2D, x: f64, y: f64) -> (f64, f64) {
+    (m[0] * x + m[1] * y + m[2], m[3] * x + m[4] * y + m[5])
+}
+
+/// Sprite đã decode được dùng chung giữa các frame
+pub(crate) struct SpriteCache<'a> {
+    index: &'a GuidIndex,
+    sprites: HashMap<(String, i64), Option<Arc<LoadedSprite>>>,
+}
+
+impl<'a> SpriteCache<'a> {
+    pub fn new(index: &'a GuidIndex) -> Self {
+        SpriteCache {
+            index,
+            sprites: HashMap::new(),
+        }
+    }
+
+    pub fn get(&mut self, sprite_guid: &str, file_id: i64) -> Option<Arc<LoadedSprite>> {
+        if sprite_guid.is_empty() {
+            return None;
+        }
+        self.sprites
+            .entry((sprite_guid.to_string(), file_id))
+            .or_insert_with(|| {
+                load_sprite(sprite_guid, file_id, self.index)
+                    .ok()
+                    .map(Arc::new)
+            })
+            .clone()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct RenderSprite {
+    pub sprite: Option<Arc<LoadedSprite>>,
+    /// RGBA tint, 0..1
+    pub color: [f64; 4],
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub sorting_order: i64,
+}
+
+/// Cây node tối giản cho việc vẽ, tạo từ HierarchyNode và có thể áp pose animation
+#[derive(Clone)]
+pub(crate) struct RenderNode {
+    pub name: String,
+    pub active: bool,
+    pub position: [f64; 3],
+    pub rotation: [f64; 4],
+    pub scale: [f64; 3],
+    pub sprite: Option<RenderSprite>,
+    pub children: Vec<RenderNode>,
+}
+
+impl RenderNode {
+    pub fn from_hierarchy(node: &HierarchyNode, cache: &mut SpriteCache) -> RenderNode {
+        let (position, rotation, scale) = match &node.transform {
+            Some(t) => (
+                [t.local_position.x, t.local_position.y, t.local_position.z],
+                [
+                    t.local_rotation.x,
+                    t.local_rotation.y,
+                    t.local_rotation.z,
+                    t.local_rotation.w.unwrap_or(1.0),
+                ],
+                [t.local_scale.x, t.local_scale.y, t.local_scale.z],
+            ),
+            None => ([0.0; 3], [0.0, 0.0, 0.0, 1.0], [1.0; 3]),
+        };
+        let sprite = node.sprite_renderer.as_ref().map(|sr| RenderSprite {
+            sprite: cache.get(&sr.sprite_guid, sr.sprite_file_id),
+            color: [sr.color.r, sr.color.g, sr.color.b, sr.color.a],
+            flip_x: sr.flip_x != 0,
+            flip_y: sr.flip_y != 0,
+            sorting_order: sr.sorting_order,
+        });
+        RenderNode {
+            name: node.name.clone(),
+            active: node.is_active != 0,
+            position,
+            rotation,
+            scale,
+            sprite,
+            children: node
+                .children
+                .iter()
+                .map(|child| RenderNode::from_hierarchy(child, cache))
+                .collect(),
+        }
+    }
+
+    /// Find a descendant by transform path ("" is the node itself)
+    pub fn find_mut(&mut self, path: &str) -> Option<&mut RenderNode> {
+        let mut node = self;
+        for name in path.split('/').filter(|n| !n.is_empty()) {
+            node = node.children.iter_mut().find(|c| c.name == name)?;
+        }
+        Some(node)
+    }
+
+    /// Override transform, SpriteRenderer and GameObject properties with a sampled pose
+    pub fn apply_pose(&mut self, pose: &TransformPose, cache: &mut SpriteCache) {
+        if let Some(position) = pose.local_position {
+            self.position = position;
+        }
+        if let Some(euler) = pose.local_euler_angles {
+            self.rotation = euler_to_quaternion(euler);
+        }
+        if let Some(rotation) = pose.local_rotation {
+            self.rotation = rotation;
+        }
+        if let Some(scale) = pose.local_scale {
+            self.scale = scale;
+        }
+        if let Some(active) = pose.floats.get("m_IsActive") {
+            self.active = *active > 0.5;
+        }
+        let Some(sprite) = self.sprite.as_mut() else {
+            return;
+        };
+        for (attribute, value) in &pose.floats {
+            match attribute.as_str() {
+                "m_Color.r" => sprite.color[0] = *value,
+                "m_Color.g" => sprite.color[1] = *value,
+                "m_Color.b" => sprite.color[2] = *value,
+                "m_Color.a" => sprite.color[3] = *value,
+                "m_FlipX" => sprite.flip_x = *value > 0.5,
+                "m_FlipY" => sprite.flip_y = *value > 0.5,
+                "m_SortingOrder" => sprite.sorting_order = value.round() as i64,
+                _ => {}
+            }
+        }
+        if let Some(keyframe) = &pose.sprite {
+            if let Some(guid) = &keyframe.guid {
+                sprite.sprite = cache.get(guid, keyframe.file_id);
+            }
+        }
+    }
+
+    /// Largest pixels-per-unit among visible sprites, used as the default output resolution
+    pub fn max_pixels_per_unit(&self) -> Option<f64> {
+        let own = self
+            .sprite
+            .as_ref()
+            .and_then(|s| s.sprite.as_ref())
+            .map(|s| s.pixels_per_unit as f64);
+        self.children
+            .iter()
+            .filter_map(|c| c.max_pixels_per_unit())
+            .chain(own)
+            .reduce(f64::max)
+    }
+}
+
+pub(crate) struct DrawItem {
+    sprite: Arc<LoadedSprite>,
+    color: [f64; 4],
+    /// Image pixel (top-left origin) -> world XY
+    image_to_world: Affine2D,
+    sorting_order: i64,
+    depth: f64,
+    order: usize,
+}
+
+// Ma trận từ pixel của ảnh sprite sang không gian local (đơn vị Unity), pivot nằm ở gốc
+fn sprite_matrix(sprite: &LoadedSprite, flip_x: bool, flip_y: bool) -> Affine {
+    let ppu = sprite.pixels_per_unit.max(0.0001) as f64;
+    let height = sprite.image.height() as f64;
+    let left =
+        sprite.texture_rect_offset.x as f64 - sprite.pivot.x as f64 * sprite.rect.width as f64;
+    let bottom =
+        sprite.texture_rect_offset.y as f64 - sprite.pivot.y as f64 * sprite.rect.height as f64;
+    let fx = if flip_x { -1.0 } else { 1.0 };
+    let fy = if flip_y { -1.0 } else { 1.0 };
+    [
+        [fx / ppu, 0.0, 0.0, fx * left / ppu],
+        [0.0, -fy / ppu, 0.0, fy * (height + bottom) / ppu],
+        [0.0, 0.0, 1.0, 0.0],
+    ]
+}
+
+fn collect_draw_items(node: &RenderNode, parent: &Affine, items: &mut Vec<DrawItem>) {
+    if !node.active {
+        return;
+    }
+    let world = multiply(parent, &trs(node.position, node.rotation, node.scale));
+    if let Some(render_sprite) = &node.sprite {
+        if let Some(sprite) = &render_sprite.sprite {
+            let m = multiply(
+                &world,
+                &sprite_matrix(sprite, render_sprite.flip_x, render_sprite.flip_y),
+            );
+            items.push(DrawItem {
+                sprite: sprite.clone(),
+                color: render_sprite.color,
+                image_to_world: [m[0][0], m[0][1], m[0][3], m[1][0], m[1][1], m[1][3]],
+                sorting_order: render_sprite.sorting_order,
+                depth: world[2][3],
+                order: items.len(),
+            });
+        }
+    }
+    for child in &node.children {
+        collect_draw_items(child, &world, items);
+    }
+}
+
+/// Visible sprites of a tree in draw order (sorting order, then depth, then hierarchy order)
+pub(crate) fn draw_items(root: &RenderNode) -> Vec<DrawItem> {
+    let mut items = Vec::new();
+    collect_draw_items(root, &IDENTITY, &mut items);
+    items.sort_by(|a, b| {
+        a.sorting_order
+            .cmp(&b.sorting_order)
+            .then(b.depth.total_cmp(&a.depth))
+            .then(a.order.cmp(&b.order))
+    });
+    items
+}
+
+/// Axis-aligned world bounds `[min_x, min_y, max_x, max_y]`
+pub(crate) fn world_bounds(items: &[DrawItem]) -> Option<[f64; 4]> {
+    let mut bounds: Option<[f64; 4]> = None;
+    for item in items {
+        let (w, h) = item.sprite.image.dimensions();
+        for (x, y) in [
+            (0.0, 0.0),
+            (w as f64, 0.0),
+            (0.0, h as f64),
+            (w as f64, h as f64),
+        ] {
+            let (wx, wy) = apply_2d(&item.image_to_world, x, y);
+            bounds = Some(match bounds {
+                Some([x0, y0, x1, y1]) => [x0.min(wx), y0.min(wy), x1.max(wx), y1.max(wy)],
+                None => [wx, wy, wx, wy],
+            });
+        }
+    }
+    bounds
+}
+
+fn merge_bounds(a: Option<[f64; 4]>, b: Option<[f64; 4]>) -> Option<[f64; 4]> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some([
+            a[0].min(b[0]),
+            a[1].min(b[1]),
+            a[2].max(b[2]),
+            a[3].max(b[3]),
+        ]),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Vùng vẽ: world bounds -> pixel
+pub(crate) struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    /// World XY -> canvas pixel (top-left origin)
+    world_to_canvas: Affine2D,
+}
+
+impl Canvas {
+    pub fn new(bounds: [f64; 4], pixels_per_unit: f64, padding: u32) -> Result<Canvas, String> {
+        let [min_x, min_y, max_x, max_y] = bounds;
+        let padding = padding as f64;
+        // Trừ epsilon để sai số float (240.00000001) không làm dư 1 pixel
+        let width = ((max_x - min_x) * pixels_per_unit + padding * 2.0 - 1e-6)
+            .ceil()
+            .max(1.0);
+        let height = ((max_y - min_y) * pixels_per_unit + padding * 2.0 - 1e-6)
+            .ceil()
+            .max(1.0);
+        if width > MAX_CANVAS_SIZE as f64 || height > MAX_CANVAS_SIZE as f64 {
+            return Err(format!(
+                "Render size {}x{} exceeds the {}px limit, lower the scale",
+                width, height, MAX_CANVAS_SIZE
+            ));
+        }
+        Ok(Canvas {
+            width: width as u32,
+            height: height as u32,
+            world_to_canvas: [
+                pixels_per_unit,
+                0.0,
+                padding - min_x * pixels_per_unit,
+                0.0,
+                -pixels_per_unit,
+                padding + max_y * pixels_per_unit,
+            ],
+        })
+    }
+
+    pub fn draw(&self, items: &[DrawItem]) -> RgbaImage {
+        let mut output = RgbaImage::new(self.width, self.height);
+        for item in items {
+            draw_item(&mut output, item, &self.world_to_canvas);
+        }
+        output
+    }
+}
+
+// Lấy mẫu bilinear với alpha premultiplied để không bị viền đen
+fn sample_premultiplied(image: &RgbaImage, x: f64, y: f64) -> [f64; 4] {
+    let (w, h) = image.dimensions();
+    let x = x - 0.5;
+    let y = y - 0.5;
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let tx = x - x0;
+    let ty = y - y0;
+    let mut result = [0.0; 4];
+    for (dx, dy, weight) in [
+        (0.0, 0.0, (1.0 - tx) * (1.0 - ty)),
+        (1.0, 0.0, tx * (1.0 - ty)),
+        (0.0, 1.0, (1.0 - tx) * ty),
+        (1.0, 1.0, tx * ty),
+    ] {
+        let px = x0 + dx;
+        let py = y0 + dy;
+        if weight <= 0.0 || px < 0.0 || py < 0.0 || px >= w as f64 || py >= h as f64 {
+            continue;
+        }
+        let p = image.get_pixel(px as u32, py as u32).0;
+        let alpha = p[3] as f64 / 255.0;
+        result[0] += p[0] as f64 / 255.0 * alpha * weight;
+        result[1] += p[1] as f64 / 255.0 * alpha * weight;
+        result[2] += p[2] as f64 / 255.0 * alpha * weight;
+        result[3] += alpha * weight;
+    }
+    result
+}
+
+fn draw_item(output: &mut RgbaImage, item: &DrawItem, world_to_canvas: &Affine2D) {
+    let m = &item.image_to_world;
+    let w2c = world_to_canvas;
+    // image pixel -> canvas pixel
+    let image_to_canvas: Affine2D = [
+        w2c[0] * m[0] + w2c[1] * m[3],
+        w2c[0] * m[1] + w2c[1] * m[4],
+        w2c[0] * m[2] + w2c[1] * m[5] + w2c[2],
+        w2c[3] * m[0] + w2c[4] * m[3],
+        w2c[3] * m[1] + w2c[4] * m[4],
+        w2c[3] * m[2] + w2c[4] * m[5] + w2c[5],
+    ];
+    let Some(canvas_to_image) = invert_2d(&image_to_canvas) else {
+        return;
+    };
+
+    let (iw, ih) = item.sprite.image.dimensions();
+    let corners = [
+        (0.0, 0.0),
+        (iw as f64, 0.0),
+        (0.0, ih as f64),
+        (iw as f64, ih as f64),
+    ]
+    .map(|(x, y)| apply_2d(&image_to_canvas, x, y));
+    let min_x = corners
+        .iter()
+        .map(|c| c.0)
+        .fold(f64::MAX, f64::min)
+        .floor()
+        .max(0.0) as u32;
+    let min_y = corners
+        .iter()
+        .map(|c| c.1)
+        .fold(f64::MAX, f64::min)
+        .floor()
+        .max(0.0) as u32;
+    let max_x =
+        (corners.iter().map(|c| c.0).fold(f64::MIN, f64::max).ceil() as u32).min(output.width());
+    let max_y =
+        (corners.iter().map(|c| c.1).fold(f64::MIN, f64::max).ceil() as u32).min(output.height());
+
+    let [tr, tg, tb, ta] = item.color.map(|c| c.clamp(0.0, 1.0));
+    for cy in min_y..max_y {
+        for cx in min_x..max_x {
+            let (ix, iy) = apply_2d(&canvas_to_image, cx as f64 + 0.5, cy as f64 + 0.5);
+            if ix < -0.5 || iy < -0.5 || ix > iw as f64 + 0.5 || iy > ih as f64 + 0.5 {
+                continue;
+            }
+            let [r, g, b, a] = sample_premultiplied(&item.sprite.image, ix, iy);
+            let (sr, sg, sb, sa) = (r * tr * ta, g * tg * ta, b * tb * ta, a * ta);
+            if sa <= 0.0 {
+                continue;
+            }
+
+            // Blend "over" lên canvas (canvas lưu straight alpha)
+            let dst = output.get_pixel_mut(cx, cy);
+            let da = dst.0[3] as f64 / 255.0;
+            let out_a = sa + da * (1.0 - sa);
+            let blend = |s: f64, d: u8| (s + d as f64 / 255.0 * da * (1.0 - sa)) / out_a;
+            dst.0 = [
+                (blend(sr, dst.0[0]) * 255.0).round().clamp(0.0, 255.0) as u8,
+                (blend(sg, dst.0[1]) * 255.0).round().clamp(0.0, 255.0) as u8,
+                (blend(sb, dst.0[2]) * 255.0).round().clamp(0.0, 255.0) as u8,
+                (out_a * 255.0).round().clamp(0.0, 255.0) as u8,
+            ];
+        }
+    }
+}
+
+fn encode_png(image: &RgbaImage) -> Result<Vec<u8>, String> {
+    let mut buf = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut buf, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(buf.into_inner())
+}
+
+/// Composite the SpriteRenderers of a hierarchy (bind pose) into one transparent image
+pub fn render_hierarchy(
+    hierarchy: &HierarchyNode,
+    index: &GuidIndex,
+    scale: f64,
+    padding: u32,
+) -> Result<RgbaImage, String> {
+    let mut cache = SpriteCache::new(index);
+    let mut root = RenderNode::from_hierarchy(hierarchy, &mut cache);
+    // Root của prefab có thể được lưu ở trạng thái inactive, vẫn vẽ như khi được spawn
+    root.active = true;
+
+    let items = draw_items(&root);
+    let bounds = world_bounds(&items).ok_or("Nothing to render: no visible sprites")?;
+    let pixels_per_unit = root
+        .max_pixels_per_unit()
+        .unwrap_or(DEFAULT_PIXELS_PER_UNIT)
+        * scale;
+    Ok(Canvas::new(bounds, pixels_per_unit, padding)?.draw(&items))
+}
+
+/// Render a prefab file straight to PNG bytes
+pub async fn render_prefab_png(
+    prefab_path: String,
+    index: &GuidIndex,
+    scale: f64,
+    padding: u32,
+) -> Result<Vec<u8>, String> {
+    let prefab = read_prefab(prefab_path, index).await?;
+    let hierarchy = build_hierarchy(&prefab, index)?;
+    encode_png(&render_hierarchy(&hierarchy, index, scale, padding)?)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageRenderOptions {
+    /// PNG file to write; the image is returned as base64 when omitted
+    pub output_path: Option<String>,
+    /// Multiplier applied to the sprites' pixels-per-unit
+    pub scale: Option<f64>,
+    pub padding: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedImage {
+    pub width: u32,
+    pub height: u32,
+    pub output_path: Option<String>,
+    pub base64: Option<String>,
+}
+
+#[tauri::command]
+pub async fn render_prefab_image(
+    project_path: String,
+    prefab_path: String,
+    options: Option<ImageRenderOptions>,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<RenderedImage, String> {
+    let options = options.unwrap_or_default();
+    let index = project_index(&guid_index, &project_path).await?;
+    let prefab = read_prefab(prefab_path, &index).await?;
+    let hierarchy = build_hierarchy(&prefab, &index)?;
+    let image = render_hierarchy(
+        &hierarchy,
+        &index,
+        options.scale.filter(|s| *s > 0.0).unwrap_or(1.0),
+        options.padding.unwrap_or(0),
+    )?;
+    let (width, height) = image.dimensions();
+
+    let Some(output_path) = options.output_path else {
+        let base64_str = general_purpose::STANDARD.encode(encode_png(&image)?);
+        return Ok(RenderedImage {
+            width,
+            height,
+            output_path: None,
+            base64: Some(format!("data:image/png;base64,{}", base64_str)),
+        });
+    };
+
+    let mut writer = create_output_file(Path::new(&output_path))?;
+    writer
+        .write_all(&encode_png(&image)?)
+        .map_err(|e| format!("Failed to write image file: {}", e))?;
+    Ok(RenderedImage {
+        width,
+        height,
+        output_path: Some(output_path),
+        base64: None,
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RenderFormat {
+    PngSequence,
+    Gif,
+    Apng,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AnimationRenderOptions {
+    /// Clip chọn theo path, GUID, hoặc tên clip trong AnimatorController của prefab
+    pub clip_path: Option<String>,
+    pub clip_guid: Option<String>,
+    pub clip_name: Option<String>,
+    /// File for GIF/APNG, directory for a PNG sequence
+    pub output_path: String,
+    pub format: RenderFormat,
+    /// Defaults to the clip's sample rate
+    pub fps: Option<f64>,
+    /// Multiplier applied to the sprites' pixels-per-unit
+    pub scale: Option<f64>,
+    pub padding: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedAnimation {
+    pub clip_name: String,
+    pub frame_count: usize,
+    pub fps: f64,
+    pub width: u32,
+    pub height: u32,
+    pub output_paths: Vec<String>,
+}
+
+fn find_clip_path(
+    options: &AnimationRenderOptions,
+    hierarchy: &HierarchyNode,
+    index: &GuidIndex,
+) -> Result<PathBuf, String> {
+    if options.clip_path.is_some() || options.clip_guid.is_some() {
+        return index
+            .resolve_path_or_guid(options.clip_path.as_deref(), options.clip_guid.as_deref());
+    }
+    let clip_name = options
+        .clip_name
+        .as_deref()
+        .ok_or("A clip path, GUID or name is required")?;
+
+    // Tìm clip theo tên trong controller của Animator đầu tiên
+    fn controller_guid(node: &HierarchyNode) -> Option<&str> {
+        node.animator
+            .as_ref()
+            .map(|a| a.controller_guid.as_str())
+            .filter(|g| !g.is_empty())
+            .or_else(|| node.children.iter().find_map(controller_guid))
+    }
+    let guid = controller_guid(hierarchy).ok_or("Prefab has no Animator controller")?;
+    let controller_path = index
+        .resolve(guid)
+        .ok_or_else(|| format!("Controller not found for GUID: {}", guid))?;
+    let graph = load_animator_controller(&controller_path, index)?;
+    graph
+        .clips
+        .iter()
+        .find(|c| c.name == clip_name)
+        .and_then(|c| c.path.as_ref())
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("Clip '{}' not found in {}", clip_name, graph.name))
+}
+
+fn write_png_sequence(
+    frames: &AnimationFrames,
+    output_dir: &Path,
+    name: &str,
+) -> Result<Vec<String>, String> {
+    fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    frames
+        .iter()
+        .enumerate()
+        .map(|(i, frame)| {
+            let path = output_dir.join(format!("{}_{:04}.png", name, i));
+            frame
+                .save(&path)
+                .map_err(|e| format!("Failed to write frame {}: {}", i, e))?;
+            Ok(path.to_string_lossy().to_string())
+        })
+        .collect()
+}
+
+fn create_output_file(output_path: &Path) -> Result<BufWriter<File>, String> {
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+    let file =
+        File::create(output_path).map_err(|e| format!("Failed to create output file: {}", e))?;
+    Ok(BufWriter::new(file))
+}
+
+fn write_gif(frames: &AnimationFrames, output_path: &Path, fps: f64) -> Result<(), String> {
+    let mut encoder = GifEncoder::new_with_speed(create_output_file(output_path)?, 10);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(|e| format!("Failed to write GIF: {}", e))?;
+    let delay = Delay::from_numer_denom_ms((1_000_000.0 / fps).round() as u32, 1000);
+    for frame in frames.iter() {
+        encoder
+            .encode_frame(Frame::from_parts(frame, 0, 0, delay))
+            .map_err(|e| format!("Failed to write GIF frame: {}", e))?;
+    }
+    Ok(())
+}
+
+fn write_apng(frames: &AnimationFrames, output_path: &Path, fps: f64) -> Result<(), String> {
+    let mut encoder = png::Encoder::new(
+        create_output_file(output_path)?,
+        frames.width(),
+        frames.height(),
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let to_error = |e: png::EncodingError| format!("Failed to write APNG: {}", e);
+    encoder
+        .set_animated(frames.frame_count() as u32, 0)
+        .map_err(to_error)?;
+    encoder
+        .set_frame_delay(
+            (1000.0 / fps).round().clamp(1.0, u16::MAX as f64) as u16,
+            1000,
+        )
+        .map_err(to_error)?;
+    let mut writer = encoder.write_header().map_err(to_error)?;
+    for frame in frames.iter() {
+        writer.write_image_data(frame.as_raw()).map_err(to_error)?;
+    }
+    writer.finish().map_err(to_error)
+}
+
+/// A sampled clip: the shared canvas plus what to draw on each frame.
+/// Frames are rasterized one at a time while they are written, never all at once
+pub struct AnimationFrames {
+    pub clip_name: String,
+    pub fps: f64,
+    canvas: Canvas,
+    frame_items: Vec<Vec<DrawItem>>,
+}
+
+impl AnimationFrames {
+    pub fn frame_count(&self) -> usize {
+        self.frame_items.len()
+    }
+
+    pub fn width(&self) -> u32 {
+        self.canvas.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.canvas.height
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = RgbaImage> + '_ {
+        self.frame_items.iter().map(|items| self.canvas.draw(items))
+    }
+}
+
+/// Sample every frame of a clip played on a prefab
+pub fn render_animation_frames(
+    prefab: &HierarchyNode,
+    clip_path: &Path,
+    index: &GuidIndex,
+    fps: Option<f64>,
+    scale: f64,
+    padding: u32,
+) -> Result<AnimationFrames, String> {
+    let clip = load_animation_clip(clip_path, index, false)?;
+    let fps = fps.filter(|f| *f > 0.0).unwrap_or(clip.sample_rate);
+
+    let mut cache = SpriteCache::new(index);
+    let base = RenderNode::from_hierarchy(prefab, &mut cache);
+    // Path trong clip tính từ GameObject có Animator
+    let animated_root = animator_path(prefab).unwrap_or_default();
+
+    let mut frame_items = Vec::new();
+    let mut bounds = None;
+    for time in frame_times(&clip, fps) {
+        let mut tree = base.clone();
+        for pose in sample_clip(&clip, time).transforms {
+            let path = match (animated_root.is_empty(), pose.path.is_empty()) {
+                (true, _) => pose.path.clone(),
+                (false, true) => animated_root.clone(),
+                (false, false) => format!("{}/{}", animated_root, pose.path),
+            };
+            if let Some(node) = tree.find_mut(&path) {
+                node.apply_pose(&pose, &mut cache);
+            }
+        }
+        let items = draw_items(&tree);
+        bounds = merge_bounds(bounds, world_bounds(&items));
+        frame_items.push(items);
+    }
+
+    let bounds = bounds.ok_or("Nothing to render: no visible sprites")?;
+    let pixels_per_unit = base
+        .max_pixels_per_unit()
+        .unwrap_or(DEFAULT_PIXELS_PER_UNIT)
+        * scale;
+    Ok(AnimationFrames {
+        clip_name: clip.name,
+        fps,
+        canvas: Canvas::new(bounds, pixels_per_unit, padding)?,
+        frame_items,
+    })
+}
+
+// Path (tính từ root) của node đầu tiên có Animator
+fn animator_path(node: &HierarchyNode) -> Option<String> {
+    if node.animator.is_some() {
+        return Some(String::new());
+    }
+    node.children.iter().find_map(|child| {
+        animator_path(child).map(|rest| {
+            if rest.is_empty() {
+                child.name.clone()
+            } else {
+                format!("{}/{}", child.name, rest)
+            }
+        })
+    })
+}
+
+#[tauri::command]
+pub async fn render_prefab_animation(
+    project_path: String,
+    prefab_path: String,
+    options: AnimationRenderOptions,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<RenderedAnimation, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let prefab = read_prefab(prefab_path, &index).await?;
+    let hierarchy = build_hierarchy(&prefab, &index)?;
+    let clip_path = find_clip_path(&options, &hierarchy, &index)?;
+
+    let frames = render_animation_frames(
+        &hierarchy,
+        &clip_path,
+        &index,
+        options.fps,
+        options.scale.filter(|s| *s > 0.0).unwrap_or(1.0),
+        options.padding.unwrap_or(0),
+    )?;
+
+    let output_path = PathBuf::from(&options.output_path);
+    let output_paths = match options.format {
+        RenderFormat::PngSequence => write_png_sequence(&frames, &output_path, &frames.clip_name)?,
+        RenderFormat::Gif => {
+            write_gif(&frames, &output_path, frames.fps)?;
+            vec![options.output_path.clone()]
+        }
+        RenderFormat::Apng => {
+            write_apng(&frames, &output_path, frames.fps)?;
+            vec![options.output_path.clone()]
+        }
+    };
+    println!(
+        "[Render] {} frames of '{}' written to {}",
+        frames.frame_count(),
+        frames.clip_name,
+        options.output_path
+    );
+
+    Ok(RenderedAnimation {
+        frame_count: frames.frame_count(),
+        fps: frames.fps,
+        width: frames.width(),
+        height: frames.height(),
+        clip_name: frames.clip_name,
+        output_paths,
+    })
+}
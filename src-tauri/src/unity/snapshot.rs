@@ -0,0 +1,341 @@
+use crate::unity::document::class_name;
+use crate::unity::guid_index::{project_index, GuidIndex, GuidIndexState, INDEX_DIR};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+
+// Extension -> main asset type, giống AssetDatabase.GetMainAssetTypeAtPath
+const ASSET_TYPES: &[(&str, &str)] = &[
+    ("prefab", "GameObject"),
+    ("unity", "SceneAsset"),
+    ("anim", "AnimationClip"),
+    ("controller", "AnimatorController"),
+    ("overridecontroller", "AnimatorOverrideController"),
+    ("mat", "Material"),
+    ("cs", "MonoScript"),
+    ("shader", "Shader"),
+    ("png", "Texture2D"),
+    ("jpg", "Texture2D"),
+    ("jpeg", "Texture2D"),
+    ("tga", "Texture2D"),
+    ("psd", "Texture2D"),
+    ("exr", "Texture2D"),
+    ("wav", "AudioClip"),
+    ("mp3", "AudioClip"),
+    ("ogg", "AudioClip"),
+    ("txt", "TextAsset"),
+    ("json", "TextAsset"),
+    ("csv", "TextAsset"),
+    ("bytes", "TextAsset"),
+    ("xml", "TextAsset"),
+    ("ttf", "Font"),
+    ("otf", "Font"),
+    ("fbx", "GameObject"),
+    ("obj", "GameObject"),
+    ("spriteatlas", "SpriteAtlas"),
+    ("asmdef", "AssemblyDefinitionAsset"),
+    ("dll", "Assembly"),
+];
+
+/// One asset of a snapshot, like `AssetInfo` in ProjectAnalyzer.cs
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetSnapshot {
+    pub path: String,
+    pub guid: String,
+    #[serde(rename = "type")]
+    pub asset_type: String,
+    pub file_size: u64,
+    /// SHA-256 of the file content, `None` for folders
+    pub hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSnapshot {
+    /// Version folder name (`<out>/<version>`)
+    pub version: String,
+    pub created_at: String,
+    pub assets: Vec<AssetSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotInfo {
+    pub output_path: String,
+    pub version: String,
+    pub asset_count: usize,
+    pub total_size: u64,
+}
+
+/// Same GUID, new path
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedAsset {
+    pub guid: String,
+    pub from: String,
+    pub to: String,
+    /// Content changed as well
+    pub modified: bool,
+}
+
+/// Same GUID and path, different content
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifiedAsset {
+    pub guid: String,
+    pub path: String,
+    pub old_size: u64,
+    pub new_size: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeChanges {
+    pub added: Vec<AssetSnapshot>,
+    pub removed: Vec<AssetSnapshot>,
+    pub moved: Vec<MovedAsset>,
+    pub modified: Vec<ModifiedAsset>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotComparison {
+    pub previous_version: String,
+    pub current_version: String,
+    pub added: usize,
+    pub removed: usize,
+    pub moved: usize,
+    pub modified: usize,
+    /// Changes grouped by asset type (`Texture2D`, `GameObject`...)
+    pub by_type: BTreeMap<String, TypeChanges>,
+}
+
+fn hash_file(path: &Path) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+// `.asset` có thể là bất kỳ type nào: đọc class ID ở header document đầu tiên
+fn native_asset_type(path: &Path) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let header = BufReader::new(file)
+        .lines()
+        .take(20)
+        .map_while(Result::ok)
+        .find(|line| line.starts_with("--- !u!"))?;
+    let class_id: i64 = header
+        .trim_start_matches("--- !u!")
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()?;
+    class_name(class_id).map(str::to_string)
+}
+
+fn asset_type(path: &Path) -> String {
+    if path.is_dir() {
+        return "DefaultAsset".to_string();
+    }
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if extension == "asset" {
+        return native_asset_type(path).unwrap_or_else(|| "Unknown".to_string());
+    }
+    ASSET_TYPES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, t)| t.to_string())
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+/// Path, GUID, type, size and content hash of every asset in the index
+pub fn snapshot_index(index: &GuidIndex) -> Result<ProjectSnapshot, String> {
+    let mut assets = Vec::with_capacity(index.asset_count());
+    for (guid, entry) in index.entries() {
+        let full_path = index.root().join(&entry.path);
+        let (file_size, hash) = if full_path.is_file() {
+            let size = fs::metadata(&full_path).map(|m| m.len()).unwrap_or(0);
+            (size, Some(hash_file(&full_path)?))
+        } else {
+            (0, None)
+        };
+        assets.push(AssetSnapshot {
+            path: entry.path.clone(),
+            guid: guid.clone(),
+            asset_type: asset_type(&full_path),
+            file_size,
+            hash,
+        });
+    }
+    assets.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(ProjectSnapshot {
+        version: index
+            .root()
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        created_at: chrono::Local::now().to_rfc3339(),
+        assets,
+    })
+}
+
+fn default_snapshot_path(project_path: &Path) -> PathBuf {
+    project_path.join(INDEX_DIR).join(SNAPSHOT_FILE)
+}
+
+// Nhận file snapshot hoặc thư mục version của c2u (dùng snapshot đã lưu nếu có)
+fn load_snapshot(path: &str) -> Result<ProjectSnapshot, String> {
+    let path = Path::new(path);
+    let snapshot_path = if path.is_dir() {
+        let saved = default_snapshot_path(path);
+        if !saved.is_file() {
+            return snapshot_index(&GuidIndex::build(&path.to_string_lossy())?);
+        }
+        saved
+    } else {
+        path.to_path_buf()
+    };
+    let content = fs::read_to_string(&snapshot_path)
+        .map_err(|e| format!("Failed to read snapshot {}: {}", snapshot_path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| {
+        format!(
+            "Failed to parse snapshot {}: {}",
+            snapshot_path.display(),
+            e
+        )
+    })
+}
+
+fn content_changed(old: &AssetSnapshot, new: &AssetSnapshot) -> bool {
+    match (&old.hash, &new.hash) {
+        (Some(a), Some(b)) => a != b,
+        _ => old.file_size != new.file_size,
+    }
+}
+
+/// Added, removed, moved and modified assets between two snapshots, keyed by GUID
+pub fn compare(previous: &ProjectSnapshot, current: &ProjectSnapshot) -> SnapshotComparison {
+    let old_assets: HashMap<&str, &AssetSnapshot> = previous
+        .assets
+        .iter()
+        .map(|a| (a.guid.as_str(), a))
+        .collect();
+    let new_assets: HashMap<&str, &AssetSnapshot> = current
+        .assets
+        .iter()
+        .map(|a| (a.guid.as_str(), a))
+        .collect();
+
+    let mut result = SnapshotComparison {
+        previous_version: previous.version.clone(),
+        current_version: current.version.clone(),
+        added: 0,
+        removed: 0,
+        moved: 0,
+        modified: 0,
+        by_type: BTreeMap::new(),
+    };
+    for asset in &current.assets {
+        let changes = result.by_type.entry(asset.asset_type.clone()).or_default();
+        match old_assets.get(asset.guid.as_str()) {
+            None => {
+                changes.added.push(asset.clone());
+                result.added += 1;
+            }
+            Some(old) if old.path != asset.path => {
+                changes.moved.push(MovedAsset {
+                    guid: asset.guid.clone(),
+                    from: old.path.clone(),
+                    to: asset.path.clone(),
+                    modified: content_changed(old, asset),
+                });
+                result.moved += 1;
+            }
+            Some(old) if content_changed(old, asset) => {
+                changes.modified.push(ModifiedAsset {
+                    guid: asset.guid.clone(),
+                    path: asset.path.clone(),
+                    old_size: old.file_size,
+                    new_size: asset.file_size,
+                });
+                result.modified += 1;
+            }
+            Some(_) => {}
+        }
+    }
+    for asset in &previous.assets {
+        if !new_assets.contains_key(asset.guid.as_str()) {
+            let changes = result.by_type.entry(asset.asset_type.clone()).or_default();
+            changes.removed.push(asset.clone());
+            result.removed += 1;
+        }
+    }
+    result.by_type.retain(|_, c| {
+        !(c.added.is_empty() && c.removed.is_empty() && c.moved.is_empty() && c.modified.is_empty())
+    });
+    result
+}
+
+#[tauri::command]
+pub async fn snapshot_project(
+    project_path: String,
+    output_path: Option<String>,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<SnapshotInfo, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let snapshot = snapshot_index(&index)?;
+    let output_path = output_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_snapshot_path(index.root()));
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    fs::write(&output_path, json).map_err(|e| format!("Failed to write snapshot: {}", e))?;
+
+    Ok(SnapshotInfo {
+        output_path: output_path.to_string_lossy().to_string(),
+        version: snapshot.version,
+        asset_count: snapshot.assets.len(),
+        total_size: snapshot.assets.iter().map(|a| a.file_size).sum(),
+    })
+}
+
+/// `previous_path`/`current_path`: snapshot JSON files or c2u version folders
+#[tauri::command]
+pub async fn compare_snapshots(
+    previous_path: String,
+    current_path: String,
+) -> Result<SnapshotComparison, String> {
+    let previous = load_snapshot(&previous_path)?;
+    let current = load_snapshot(&current_path)?;
+    Ok(compare(&previous, &current))
+}
@@ -0,0 +1,236 @@
+use crate::unity::document::parse_unity_documents;
+use crate::unity::error::{deserialize_document, UnityParseError};
+use crate::unity::guid_index::GuidIndex;
+use crate::unity::prefab::{
+    FileReference, Rect, RenderData, RenderDataKey, Sprite, Vector2, SPRITE_FILE_ID,
+};
+use image::{imageops, RgbaImage};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+// SpritePackingRotation trong settingsRaw (bit 2-5)
+const ROTATION_FLIP_HORIZONTAL: u32 = 1;
+const ROTATION_FLIP_VERTICAL: u32 = 2;
+const ROTATION_180: u32 = 3;
+const ROTATION_90: u32 = 4;
+
+/// Value of `m_RenderDataMap`: where a sprite ended up in the packed atlas texture
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasRenderData {
+    pub texture: FileReference,
+    #[serde(default)]
+    pub alpha_texture: Option<FileReference>,
+    pub texture_rect: Rect,
+    #[serde(default)]
+    pub texture_rect_offset: Option<Vector2>,
+    #[serde(default)]
+    pub settings_raw: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RenderDataEntry {
+    first: RenderDataKey,
+    second: AtlasRenderData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawSpriteAtlas {
+    #[serde(rename = "m_IsVariant", default)]
+    is_variant: i32,
+    #[serde(rename = "m_RenderDataMap", default)]
+    render_data_map: Vec<RenderDataEntry>,
+}
+
+/// A `.spriteatlas` asset (class 687078895)
+#[derive(Debug, Clone)]
+pub struct SpriteAtlas {
+    /// Variants hold a downscaled copy of their master atlas
+    pub is_variant: bool,
+    pub render_data: HashMap<RenderDataKey, AtlasRenderData>,
+}
+
+impl SpriteAtlas {
+    pub fn read(path: &Path) -> Result<SpriteAtlas, UnityParseError> {
+        let content = fs::read_to_string(path).map_err(|e| UnityParseError::Yaml {
+            file_id: 0,
+            message: format!("Failed to read sprite atlas {}: {}", path.display(), e),
+        })?;
+        let document = parse_unity_documents(&content)
+            .into_iter()
+            .find(|d| d.type_name == "SpriteAtlas")
+            .ok_or_else(|| UnityParseError::Yaml {
+                file_id: 0,
+                message: format!("{} has no SpriteAtlas document", path.display()),
+            })?;
+        if let Some(error) = document.error {
+            return Err(error);
+        }
+        let raw: RawSpriteAtlas = deserialize_document(document.file_id, &document.body)?;
+
+        Ok(SpriteAtlas {
+            is_variant: raw.is_variant != 0,
+            render_data: raw
+                .render_data_map
+                .into_iter()
+                .map(|entry| (entry.first, entry.second))
+                .collect(),
+        })
+    }
+
+    /// Render data for `key`, or for the main sprite of `sprite_guid` when the key is empty
+    pub fn lookup(&self, key: &RenderDataKey, sprite_guid: &str) -> Option<&AtlasRenderData> {
+        self.render_data.get(key).or_else(|| {
+            self.render_data
+                .iter()
+                .find(|(k, _)| k.first == sprite_guid && k.second == SPRITE_FILE_ID)
+                .map(|(_, data)| data)
+        })
+    }
+}
+
+type AtlasCache = Mutex<HashMap<PathBuf, (Option<SystemTime>, Arc<SpriteAtlas>)>>;
+
+// Atlas lớn có hàng trăm sprite, chỉ parse lại khi file đổi
+fn load_atlas(path: &Path) -> Option<Arc<SpriteAtlas>> {
+    static CACHE: OnceLock<AtlasCache> = OnceLock::new();
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut cache = CACHE.get_or_init(Default::default).lock().ok()?;
+    if let Some((time, atlas)) = cache.get(path) {
+        if *time == modified {
+            return Some(atlas.clone());
+        }
+    }
+    let atlas = Arc::new(SpriteAtlas::read(path).ok()?);
+    cache.insert(path.to_path_buf(), (modified, atlas.clone()));
+    Some(atlas)
+}
+
+/// Texture and rect a sprite is cropped from
+#[derive(Debug, Clone)]
+pub(crate) struct PackedTexture {
+    pub texture_guid: String,
+    pub texture_path: String,
+    /// `(guid, path)` of the split alpha texture (ETC1), if any
+    pub alpha_texture: Option<(String, String)>,
+    pub texture_rect: Rect,
+    pub texture_rect_offset: Vector2,
+    pub settings_raw: u32,
+}
+
+fn resolve_texture(texture: &FileReference, index: &GuidIndex) -> Option<(String, String)> {
+    let guid = texture.guid.as_ref()?;
+    let path = index.resolve_str(guid)?;
+    let lower = path.to_lowercase();
+    [".png", ".jpg", ".jpeg", ".tga"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+        .then(|| (guid.clone(), path))
+}
+
+fn from_render_data(data: &RenderData, index: &GuidIndex) -> Option<PackedTexture> {
+    let (texture_guid, texture_path) = resolve_texture(&data.texture, index)?;
+    Some(PackedTexture {
+        texture_guid,
+        texture_path,
+        alpha_texture: resolve_texture(&data.alpha_texture, index),
+        texture_rect: data.texture_rect.clone(),
+        texture_rect_offset: data.texture_rect_offset.clone(),
+        settings_raw: data.settings_raw as u32,
+    })
+}
+
+fn from_atlas(data: &AtlasRenderData, index: &GuidIndex) -> Option<PackedTexture> {
+    let (texture_guid, texture_path) = resolve_texture(&data.texture, index)?;
+    Some(PackedTexture {
+        texture_guid,
+        texture_path,
+        alpha_texture: data
+            .alpha_texture
+            .as_ref()
+            .and_then(|alpha| resolve_texture(alpha, index)),
+        texture_rect: data.texture_rect.clone(),
+        texture_rect_offset: data
+            .texture_rect_offset
+            .clone()
+            .unwrap_or(Vector2 { x: 0.0, y: 0.0 }),
+        settings_raw: data.settings_raw,
+    })
+}
+
+/// Where to crop `sprite` from: `m_RD.texture` when it exists, otherwise `m_AtlasRD`,
+/// otherwise the `m_RenderDataMap` entry of the atlas that packs it
+pub(crate) fn packed_texture(
+    sprite: &Sprite,
+    sprite_guid: &str,
+    index: &GuidIndex,
+) -> Option<PackedTexture> {
+    let mut packed = find_packed_texture(sprite, sprite_guid, index)?;
+    // Sprite cũ lưu alphaTexture ở ngoài m_RD
+    if packed.alpha_texture.is_none() {
+        packed.alpha_texture = sprite
+            .alpha_texture
+            .as_ref()
+            .and_then(|alpha| resolve_texture(alpha, index));
+    }
+    Some(packed)
+}
+
+fn find_packed_texture(
+    sprite: &Sprite,
+    sprite_guid: &str,
+    index: &GuidIndex,
+) -> Option<PackedTexture> {
+    if let Some(packed) =
+        from_render_data(&sprite.rd, index).or_else(|| from_render_data(&sprite.atlas_rd, index))
+    {
+        return Some(packed);
+    }
+
+    // m_SpriteAtlas trước, sau đó các atlas gốc rồi tới variant
+    let mut atlas_paths: Vec<(bool, PathBuf)> = Vec::new();
+    if let Some(path) = sprite
+        .sprite_atlas
+        .guid
+        .as_deref()
+        .and_then(|guid| index.resolve(guid))
+    {
+        atlas_paths.push((false, path));
+    }
+    atlas_paths.extend(index.sprite_atlases().map(|path| (true, path)));
+
+    let key = &sprite.render_data_key;
+    let mut variants = Vec::new();
+    for (scanned, path) in atlas_paths {
+        let Some(atlas) = load_atlas(&path) else {
+            continue;
+        };
+        let Some(data) = atlas.lookup(key, sprite_guid) else {
+            continue;
+        };
+        if atlas.is_variant && scanned {
+            variants.push(data.clone());
+            continue;
+        }
+        if let Some(packed) = from_atlas(data, index) {
+            return Some(packed);
+        }
+    }
+    variants.iter().find_map(|data| from_atlas(data, index))
+}
+
+/// Undo the rotation/flip the packer applied (cropped image, top-left origin)
+pub(crate) fn unpack_rotation(image: RgbaImage, settings_raw: u32) -> RgbaImage {
+    match (settings_raw >> 2) & 0xF {
+        ROTATION_FLIP_HORIZONTAL => imageops::flip_horizontal(&image),
+        ROTATION_FLIP_VERTICAL => imageops::flip_vertical(&image),
+        ROTATION_180 => imageops::rotate180(&image),
+        // Packer xoay trong hệ toạ độ bottom-left, ảnh ở đây là top-left
+        ROTATION_90 => imageops::rotate90(&image),
+        _ => image,
+    }
+}
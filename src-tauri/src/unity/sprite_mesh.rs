@@ -0,0 +1,213 @@
+use crate::unity::prefab::{IndexBuffer, RenderData, VertexData};
+use image::RgbaImage;
+
+// SpriteSettings trong settingsRaw: bit 0 = packed, bit 1 = packingMode (0 = tight)
+const SETTINGS_PACKED: u32 = 1;
+const SETTINGS_RECTANGLE_PACKING: u32 = 1 << 1;
+
+// VertexFormat của channel position
+const FORMAT_FLOAT32: i32 = 0;
+const FORMAT_FLOAT16: i32 = 1;
+
+// Các stream trong _typelessdata được căn theo 16 byte
+const STREAM_ALIGN: usize = 16;
+
+/// Triangle in sprite-local units (pivot at origin, y up)
+pub(crate) type Triangle = [(f32, f32); 3];
+
+/// Tight-packed or polygon sprites: the texture rect also holds pixels of other sprites
+pub(crate) fn needs_mesh_mask(settings_raw: u32, is_polygon: bool) -> bool {
+    let tight_packed =
+        settings_raw & SETTINGS_PACKED != 0 && settings_raw & SETTINGS_RECTANGLE_PACKING == 0;
+    tight_packed || is_polygon
+}
+
+// `_typelessdata`/`m_IndexBuffer` toàn chữ số bị YAML đọc thành số (mất số 0 ở đầu):
+// lấy lại chuỗi hex trong block m_RD của file text
+fn raw_hex(content: &str, key: &str) -> Option<String> {
+    let mut in_render_data = false;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if line.len() - trimmed.len() <= 2 {
+            in_render_data = trimmed == "m_RD:";
+            continue;
+        }
+        if !in_render_data {
+            continue;
+        }
+        if let Some(value) = trimmed.strip_prefix(key).and_then(|v| v.strip_prefix(':')) {
+            let value = value.trim();
+            return (!value.is_empty()).then(|| value.to_string());
+        }
+    }
+    None
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1F) as i32;
+    let mantissa = (bits & 0x3FF) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1F => sign * f32::INFINITY,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+// Kích thước một thành phần: (U/S)Int32, Float16/(U/S)Norm16/(U/S)Int16, còn lại 8 bit
+fn format_size(format: i32) -> usize {
+    match format {
+        FORMAT_FLOAT32 | 10 | 11 => 4,
+        FORMAT_FLOAT16 | 4 | 5 | 8 | 9 => 2,
+        _ => 1,
+    }
+}
+
+// Channel 0 là position; các stream nằm nối tiếp nhau trong buffer
+fn positions(vertex_data: &VertexData, data: &[u8]) -> Option<Vec<(f32, f32)>> {
+    let position = vertex_data.channels.first()?;
+    let component_size = match position.format {
+        FORMAT_FLOAT32 => 4,
+        FORMAT_FLOAT16 => 2,
+        _ => return None,
+    };
+    if position.dimension & 0xF < 2 {
+        return None;
+    }
+    let vertex_count = vertex_data.vertex_count.max(0) as usize;
+
+    let mut stream_offset = 0;
+    let mut stride = 0;
+    for stream in 0..=position.stream {
+        stride = vertex_data
+            .channels
+            .iter()
+            .filter(|c| c.stream == stream && c.dimension & 0xF > 0)
+            .map(|c| format_size(c.format) * (c.dimension & 0xF) as usize)
+            .sum();
+        if stream == position.stream {
+            break;
+        }
+        stream_offset += vertex_count * stride;
+        stream_offset = (stream_offset + STREAM_ALIGN - 1) & !(STREAM_ALIGN - 1);
+    }
+
+    let component = |start: usize| -> Option<f32> {
+        let bytes = data.get(start..start + component_size)?;
+        Some(match component_size {
+            4 => f32::from_le_bytes(bytes.try_into().ok()?),
+            _ => f16_to_f32(u16::from_le_bytes(bytes.try_into().ok()?)),
+        })
+    };
+    (0..vertex_count)
+        .map(|v| {
+            let start = stream_offset + v * stride + position.offset as usize;
+            Some((component(start)?, component(start + component_size)?))
+        })
+        .collect()
+}
+
+/// Triangles of `m_RD` (sprite meshes use 16-bit indices), `None` if the mesh can't be decoded
+pub(crate) fn mesh_triangles(render_data: &RenderData, content: &str) -> Option<Vec<Triangle>> {
+    let vertex_hex = raw_hex(content, "_typelessdata")
+        .or_else(|| render_data.vertex_data.typeless_data.clone())?;
+    let index_hex =
+        raw_hex(content, "m_IndexBuffer").or_else(|| match &render_data.index_buffer {
+            Some(IndexBuffer::String(hex)) => Some(hex.clone()),
+            _ => None,
+        })?;
+    let vertices = positions(&render_data.vertex_data, &decode_hex(&vertex_hex)?)?;
+    let indices: Vec<usize> = decode_hex(&index_hex)?
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+        .collect();
+
+    let ranges: Vec<(usize, usize, usize)> = if render_data.sub_meshes.is_empty() {
+        vec![(0, indices.len(), 0)]
+    } else {
+        render_data
+            .sub_meshes
+            .iter()
+            // topology 0 = triangles
+            .filter(|m| m.topology == 0)
+            .map(|m| {
+                let first = m.first_byte.max(0) as usize / 2;
+                (
+                    first,
+                    m.index_count.max(0) as usize,
+                    m.base_vertex.max(0) as usize,
+                )
+            })
+            .collect()
+    };
+
+    let mut triangles = Vec::new();
+    for (first, count, base_vertex) in ranges {
+        let Some(range) = indices.get(first..first + count) else {
+            continue;
+        };
+        for triangle in range.chunks_exact(3) {
+            let corner = |i: usize| vertices.get(triangle[i] + base_vertex).copied();
+            if let (Some(a), Some(b), Some(c)) = (corner(0), corner(1), corner(2)) {
+                triangles.push([a, b, c]);
+            }
+        }
+    }
+    (!triangles.is_empty()).then_some(triangles)
+}
+
+fn edge(a: (f32, f32), b: (f32, f32), p: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+/// Clear every pixel of `image` (top-left origin) whose center is outside the mesh.
+/// `origin` is the pivot in pixels from the bottom-left of the image
+pub(crate) fn mask_to_mesh(
+    image: &mut RgbaImage,
+    triangles: &[Triangle],
+    origin: (f32, f32),
+    pixels_per_unit: f32,
+) {
+    let (width, height) = image.dimensions();
+    let mut inside = vec![false; (width * height) as usize];
+    let to_pixels = |(x, y): (f32, f32)| {
+        (
+            x * pixels_per_unit + origin.0,
+            height as f32 - (y * pixels_per_unit + origin.1),
+        )
+    };
+
+    for triangle in triangles {
+        let [a, b, c] = triangle.map(to_pixels);
+        let min_x = a.0.min(b.0).min(c.0).floor().max(0.0) as u32;
+        let max_x = (a.0.max(b.0).max(c.0).ceil().max(0.0) as u32).min(width);
+        let min_y = a.1.min(b.1).min(c.1).floor().max(0.0) as u32;
+        let max_y = (a.1.max(b.1).max(c.1).ceil().max(0.0) as u32).min(height);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = (x as f32 + 0.5, y as f32 + 0.5);
+                let (e0, e1, e2) = (edge(a, b, p), edge(b, c, p), edge(c, a, p));
+                // Chấp nhận cả hai chiều quay của tam giác
+                if (e0 >= 0.0 && e1 >= 0.0 && e2 >= 0.0) || (e0 <= 0.0 && e1 <= 0.0 && e2 <= 0.0) {
+                    inside[(y * width + x) as usize] = true;
+                }
+            }
+        }
+    }
+
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        if !inside[(y * width + x) as usize] {
+            pixel.0 = [0, 0, 0, 0];
+        }
+    }
+}
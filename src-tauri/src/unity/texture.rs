@@ -0,0 +1,289 @@
+use crate::unity::guid_index::{project_index, GuidIndexState};
+use crate::unity::prefab::{Rect, Vector2, Vector4};
+use crate::utils::yaml_to_json;
+use image::{imageops, ImageReader, RgbaImage};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tauri::State;
+use unity_yaml_rust::yaml::{Yaml, YamlLoader};
+
+// SpriteAlignment: 9 = Custom (dùng pivot đã lưu)
+const ALIGNMENT_CUSTOM: i32 = 9;
+
+// SpriteImportMode: 2 = Multiple (sprite sheet)
+const SPRITE_MODE_MULTIPLE: i32 = 2;
+
+// Class ID của Sprite trong internalIDToNameTable
+const CLASS_SPRITE: i64 = 213;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTextureImporter {
+    #[serde(default)]
+    sprite_pixels_to_units: Option<f32>,
+    #[serde(default)]
+    alignment: i32,
+    #[serde(default)]
+    sprite_pivot: Option<Vector2>,
+    #[serde(default)]
+    sprite_border: Option<Vector4>,
+    #[serde(default)]
+    sprite_mode: i32,
+    #[serde(default)]
+    sprite_sheet: Option<RawSpriteSheet>,
+    #[serde(rename = "internalIDToNameTable", default)]
+    internal_id_to_name_table: Vec<Value>,
+    #[serde(rename = "fileIDToRecycleName", default)]
+    file_id_to_recycle_name: Option<serde_json::Map<String, Value>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawSpriteSheet {
+    #[serde(default)]
+    sprites: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawSheetSprite {
+    name: String,
+    rect: Rect,
+    #[serde(default)]
+    alignment: i32,
+    #[serde(default)]
+    pivot: Option<Vector2>,
+    #[serde(default)]
+    border: Option<Vector4>,
+    #[serde(rename = "internalID", default)]
+    internal_id: Option<i64>,
+}
+
+/// One sprite of a `spriteMode: 2` sheet
+#[derive(Debug, Clone)]
+pub struct SheetSprite {
+    pub name: String,
+    /// Pixels, bottom-left origin
+    pub rect: Rect,
+    pub pivot: Vector2,
+    pub border: Vector4,
+    internal_id: Option<i64>,
+}
+
+/// Sprite settings of a texture's `.meta` (TextureImporter)
+#[derive(Debug, Clone)]
+pub struct TextureImporter {
+    pub pixels_per_unit: f32,
+    /// Normalized pivot of the single sprite
+    pub pivot: Vector2,
+    /// Left, bottom, right, top in pixels
+    pub border: Vector4,
+    /// Sub-sprites, empty unless the texture is a sprite sheet
+    pub sprites: Vec<SheetSprite>,
+    // fileID cũ (21300000, 21300002...) -> tên sprite
+    names: HashMap<i64, String>,
+}
+
+impl Default for TextureImporter {
+    // Giá trị mặc định của Unity khi import texture thành sprite
+    fn default() -> Self {
+        TextureImporter {
+            pixels_per_unit: 100.0,
+            pivot: Vector2 { x: 0.5, y: 0.5 },
+            border: Vector4 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                w: 0.0,
+            },
+            sprites: Vec::new(),
+            names: HashMap::new(),
+        }
+    }
+}
+
+impl TextureImporter {
+    /// Read `<texture_path>.meta`, `None` if it is missing or not a TextureImporter
+    pub fn read(texture_path: &str) -> Option<TextureImporter> {
+        let content = fs::read_to_string(format!("{}.meta", texture_path)).ok()?;
+        let yaml = YamlLoader::load_from_str(&content).ok()?;
+        let document = yaml.iter().find(|d| matches!(d, Yaml::Hash(_)))?;
+        let json = yaml_to_json(document).ok()?;
+        let raw: RawTextureImporter =
+            serde_json::from_value(json.get("TextureImporter")?.clone()).ok()?;
+
+        let default = TextureImporter::default();
+        let sprites = match (&raw.sprite_sheet, raw.sprite_mode) {
+            (Some(sheet), SPRITE_MODE_MULTIPLE) => sheet_sprites(sheet, &default.border),
+            _ => Vec::new(),
+        };
+        Some(TextureImporter {
+            pixels_per_unit: raw
+                .sprite_pixels_to_units
+                .unwrap_or(default.pixels_per_unit),
+            pivot: alignment_pivot(raw.alignment, raw.sprite_pivot.as_ref()),
+            border: raw.sprite_border.clone().unwrap_or(default.border),
+            names: sprite_names(&raw),
+            sprites,
+        })
+    }
+
+    /// Sheet sprite a `{fileID, guid: <this texture>}` reference points at
+    pub fn sheet_sprite(&self, file_id: i64) -> Option<&SheetSprite> {
+        self.sprites
+            .iter()
+            .find(|s| s.internal_id == Some(file_id))
+            .or_else(|| {
+                let name = self.names.get(&file_id)?;
+                self.sprites.iter().find(|s| &s.name == name)
+            })
+    }
+}
+
+// Entry lỗi schema (thiếu rect...) thì bỏ qua, không làm hỏng cả sheet
+fn sheet_sprites(sheet: &RawSpriteSheet, default_border: &Vector4) -> Vec<SheetSprite> {
+    sheet
+        .sprites
+        .iter()
+        .filter_map(|value| serde_json::from_value::<RawSheetSprite>(value.clone()).ok())
+        .map(|raw| SheetSprite {
+            pivot: alignment_pivot(raw.alignment, raw.pivot.as_ref()),
+            border: raw.border.unwrap_or_else(|| default_border.clone()),
+            name: raw.name,
+            rect: raw.rect,
+            internal_id: raw.internal_id,
+        })
+        .collect()
+}
+
+// Key số trong YAML được yaml_to_json ghi thành "Integer(213)"
+fn integer_key(key: &str) -> Option<i64> {
+    key.strip_prefix("Integer(")
+        .and_then(|k| k.strip_suffix(')'))
+        .unwrap_or(key)
+        .parse()
+        .ok()
+}
+
+// Unity cũ tham chiếu sprite qua fileIDToRecycleName, bản mới qua internalIDToNameTable
+fn sprite_names(raw: &RawTextureImporter) -> HashMap<i64, String> {
+    let mut names = HashMap::new();
+    for (key, name) in raw.file_id_to_recycle_name.iter().flatten() {
+        if let (Some(file_id), Some(name)) = (integer_key(key), name.as_str()) {
+            names.insert(file_id, name.to_string());
+        }
+    }
+    for entry in &raw.internal_id_to_name_table {
+        let Some(name) = entry.get("second").and_then(Value::as_str) else {
+            continue;
+        };
+        for (class_id, file_id) in entry
+            .get("first")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            if integer_key(class_id) == Some(CLASS_SPRITE) {
+                if let Some(file_id) = file_id.as_i64() {
+                    names.insert(file_id, name.to_string());
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Crop a sheet sprite out of its texture (top-left origin image, bottom-left origin rect)
+pub(crate) fn crop_sheet_sprite(texture: &RgbaImage, sprite: &SheetSprite) -> RgbaImage {
+    let x = sprite.rect.x.max(0.0) as u32;
+    let width = sprite.rect.width.max(0.0) as u32;
+    let height = sprite.rect.height.max(0.0) as u32;
+    let y = texture
+        .height()
+        .saturating_sub(sprite.rect.y.max(0.0) as u32 + height);
+    imageops::crop_imm(texture, x, y, width, height).to_image()
+}
+
+fn file_name(sprite_name: &str) -> String {
+    sprite_name.replace(['/', '\\', ':', '<', '>', '|', '?', '*', '"'], "_")
+}
+
+/// Write every sprite of a sheet as `<output>/<name>.png`, returns the written paths
+pub fn unpack_sheet(texture_path: &Path, output: &Path) -> Result<Vec<String>, String> {
+    let path = texture_path.to_string_lossy();
+    let importer = TextureImporter::read(&path)
+        .ok_or_else(|| format!("Failed to read texture importer of {}", path))?;
+    if importer.sprites.is_empty() {
+        return Err(format!("{} is not a sprite sheet", path));
+    }
+    let texture = ImageReader::open(texture_path)
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.decode().map_err(|e| e.to_string()))
+        .map_err(|e| format!("Failed to decode texture {}: {}", path, e))?
+        .to_rgba8();
+
+    fs::create_dir_all(output).map_err(|e| format!("Failed to create output directory: {}", e))?;
+    let mut written = Vec::new();
+    for sprite in &importer.sprites {
+        let target = output.join(format!("{}.png", file_name(&sprite.name)));
+        crop_sheet_sprite(&texture, sprite)
+            .save(&target)
+            .map_err(|e| format!("Failed to save sprite {}: {}", sprite.name, e))?;
+        written.push(target.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+#[tauri::command]
+pub async fn unpack_sprite_sheet(
+    project_path: String,
+    texture_path: Option<String>,
+    texture_guid: Option<String>,
+    output_dir: String,
+    guid_index: State<'_, GuidIndexState>,
+) -> Result<Vec<String>, String> {
+    let index = project_index(&guid_index, &project_path).await?;
+    let path = index.resolve_path_or_guid(texture_path.as_deref(), texture_guid.as_deref())?;
+    unpack_sheet(&path, Path::new(&output_dir))
+}
+
+/// Pivot of a `SpriteAlignment` preset, `custom` for `Custom`
+fn alignment_pivot(alignment: i32, custom: Option<&Vector2>) -> Vector2 {
+    let (x, y) = match alignment {
+        1 => (0.0, 1.0),
+        2 => (0.5, 1.0),
+        3 => (1.0, 1.0),
+        4 => (0.0, 0.5),
+        5 => (1.0, 0.5),
+        6 => (0.0, 0.0),
+        7 => (0.5, 0.0),
+        8 => (1.0, 0.0),
+        ALIGNMENT_CUSTOM => custom.map(|p| (p.x, p.y)).unwrap_or((0.5, 0.5)),
+        _ => (0.5, 0.5),
+    };
+    Vector2 { x, y }
+}
+
+/// `m_Offset` of a sprite: pivot relative to the rect center, in pixels
+pub(crate) fn pivot_offset(width: f32, height: f32, pivot: &Vector2) -> Vector2 {
+    Vector2 {
+        x: width * (pivot.x - 0.5),
+        y: height * (pivot.y - 0.5),
+    }
+}
+
+/// Copy the alpha of a split alpha texture (ETC1 builds keep it in the red channel) into `color`.
+/// The alpha texture may be smaller than the color one, it is sampled nearest-neighbour
+pub(crate) fn merge_alpha(color: &mut RgbaImage, alpha: &RgbaImage) {
+    let (width, height) = color.dimensions();
+    let (alpha_width, alpha_height) = alpha.dimensions();
+    if alpha_width == 0 || alpha_height == 0 {
+        return;
+    }
+    for (x, y, pixel) in color.enumerate_pixels_mut() {
+        let ax = (x as u64 * alpha_width as u64 / width as u64) as u32;
+        let ay = (y as u64 * alpha_height as u64 / height as u64) as u32;
+        pixel[3] = alpha.get_pixel(ax, ay)[0];
+    }
+}
@@ -0,0 +1,206 @@
+use crate::unity::prefab::{HierarchyNode, SimplifiedSpriteRenderer, SimplifiedTransform};
+use serde::{Deserialize, Serialize};
+
+/// 3x4 affine matrix (rotation/scale + translation), row major
+pub(crate) type Affine = [[f64; 4]; 3];
+
+pub(crate) const IDENTITY: Affine = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+];
+
+// SpriteDrawMode: 0 = Simple (vẽ theo rect), còn lại vẽ theo m_Size
+const DRAW_MODE_SIMPLE: i64 = 0;
+
+/// World-space axis-aligned rectangle on the XY plane, in Unity units
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldBounds {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl WorldBounds {
+    fn from_points(points: impl IntoIterator<Item = (f64, f64)>) -> Option<WorldBounds> {
+        points.into_iter().fold(None, |bounds, (x, y)| {
+            Some(match bounds {
+                Some(b) => WorldBounds {
+                    min_x: b.min_x.min(x),
+                    min_y: b.min_y.min(y),
+                    max_x: b.max_x.max(x),
+                    max_y: b.max_y.max(y),
+                },
+                None => WorldBounds {
+                    min_x: x,
+                    min_y: y,
+                    max_x: x,
+                    max_y: y,
+                },
+            })
+        })
+    }
+
+    fn union(a: Option<WorldBounds>, b: Option<WorldBounds>) -> Option<WorldBounds> {
+        match (a, b) {
+            (Some(a), Some(b)) => WorldBounds::from_points([
+                (a.min_x, a.min_y),
+                (a.max_x, a.max_y),
+                (b.min_x, b.min_y),
+                (b.max_x, b.max_y),
+            ]),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+pub(crate) fn trs(position: [f64; 3], rotation: [f64; 4], scale: [f64; 3]) -> Affine {
+    let [x, y, z, w] = rotation;
+    let r = [
+        [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y - z * w),
+            2.0 * (x * z + y * w),
+        ],
+        [
+            2.0 * (x * y + z * w),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z - x * w),
+        ],
+        [
+            2.0 * (x * z - y * w),
+            2.0 * (y * z + x * w),
+            1.0 - 2.0 * (x * x + y * y),
+        ],
+    ];
+    let mut m = IDENTITY;
+    for row in 0..3 {
+        for col in 0..3 {
+            m[row][col] = r[row][col] * scale[col];
+        }
+        m[row][3] = position[row];
+    }
+    m
+}
+
+pub(crate) fn multiply(a: &Affine, b: &Affine) -> Affine {
+    let mut m = IDENTITY;
+    for row in 0..3 {
+        for col in 0..4 {
+            let mut value = (0..3).map(|k| a[row][k] * b[k][col]).sum::<f64>();
+            if col == 3 {
+                value += a[row][3];
+            }
+            m[row][col] = value;
+        }
+    }
+    m
+}
+
+fn apply(m: &Affine, x: f64, y: f64) -> (f64, f64) {
+    (
+        m[0][0] * x + m[0][1] * y + m[0][3],
+        m[1][0] * x + m[1][1] * y + m[1][3],
+    )
+}
+
+/// Unity euler angles (degrees) to quaternion, rotation order Z, X, Y
+pub(crate) fn euler_to_quaternion(euler: [f64; 3]) -> [f64; 4] {
+    let [x, y, z] = euler.map(|a| a.to_radians() * 0.5);
+    let (sx, cx) = x.sin_cos();
+    let (sy, cy) = y.sin_cos();
+    let (sz, cz) = z.sin_cos();
+    [
+        cy * sx * cz + sy * cx * sz,
+        sy * cx * cz - cy * sx * sz,
+        cy * cx * sz - sy * sx * cz,
+        cy * cx * cz + sy * sx * sz,
+    ]
+}
+
+/// Local TRS of a transform, identity when the node has none
+pub(crate) fn local_matrix(transform: Option<&SimplifiedTransform>) -> Affine {
+    let Some(t) = transform else {
+        return IDENTITY;
+    };
+    let rotation = [
+        t.local_rotation.x,
+        t.local_rotation.y,
+        t.local_rotation.z,
+        t.local_rotation.w.unwrap_or(1.0),
+    ];
+    // Quaternion lưu trong YAML bị làm tròn, chuẩn hoá lại trước khi dựng ma trận
+    let length = rotation.iter().map(|v| v * v).sum::<f64>().sqrt();
+    let rotation = if length > 1e-9 {
+        rotation.map(|v| v / length)
+    } else {
+        [0.0, 0.0, 0.0, 1.0]
+    };
+    trs(
+        [t.local_position.x, t.local_position.y, t.local_position.z],
+        rotation,
+        [t.local_scale.x, t.local_scale.y, t.local_scale.z],
+    )
+}
+
+/// Unity `Matrix4x4` order: column major, 16 values
+fn column_major(m: &Affine) -> [f64; 16] {
+    let mut out = [0.0; 16];
+    for col in 0..4 {
+        for row in 0..3 {
+            out[col * 4 + row] = m[row][col];
+        }
+    }
+    out[15] = 1.0;
+    out
+}
+
+// Góc của sprite trong không gian local (pivot ở gốc); flip lật quanh pivot
+fn sprite_corners(sr: &SimplifiedSpriteRenderer) -> Option<[(f64, f64); 4]> {
+    let rect = sr.sprite_rect.as_ref()?;
+    let pivot = sr.pivot.as_ref()?;
+    let ppu = sr.pixels_per_unit.filter(|p| *p > 0.0)? as f64;
+    let (width, height) = if sr.draw_mode == DRAW_MODE_SIMPLE {
+        (rect.width as f64 / ppu, rect.height as f64 / ppu)
+    } else {
+        (sr.size.x, sr.size.y)
+    };
+    let left = -pivot.x as f64 * width;
+    let bottom = -pivot.y as f64 * height;
+    let fx = if sr.flip_x != 0 { -1.0 } else { 1.0 };
+    let fy = if sr.flip_y != 0 { -1.0 } else { 1.0 };
+    Some(
+        [
+            (left, bottom),
+            (left + width, bottom),
+            (left, bottom + height),
+            (left + width, bottom + height),
+        ]
+        .map(|(x, y)| (fx * x, fy * y)),
+    )
+}
+
+/// Fill `world_matrix`, `sprite_bounds` and `bounds` of a node and its descendants
+pub(crate) fn apply_world_transforms(node: &mut HierarchyNode, parent: &Affine) {
+    let world = multiply(parent, &local_matrix(node.transform.as_ref()));
+    node.world_matrix = column_major(&world);
+    node.sprite_bounds = node
+        .sprite_renderer
+        .as_ref()
+        .and_then(sprite_corners)
+        .and_then(|corners| WorldBounds::from_points(corners.map(|(x, y)| apply(&world, x, y))));
+
+    // Node inactive thì cả nhánh con không hiển thị, không tính vào bounds
+    let mut bounds = None;
+    for child in &mut node.children {
+        apply_world_transforms(child, &world);
+        bounds = WorldBounds::union(bounds, child.bounds);
+    }
+    node.bounds = if node.is_active != 0 {
+        WorldBounds::union(node.sprite_bounds, bounds)
+    } else {
+        None
+    };
+}
@@ -0,0 +1,684 @@
+use crate::unity::document::parse_unity_documents;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Một document của file Unity YAML, giữ nguyên text gốc (kể cả ký tự xuống dòng)
+struct DocumentText {
+    /// `--- !u!<class> &<id>` line, including its line ending
+    header: String,
+    file_id: i64,
+    lines: Vec<String>,
+}
+
+/// Unity YAML file kept as text, so bytes that are not edited are written back unchanged
+pub struct UnityYamlFile {
+    /// `%YAML 1.1` / `%TAG !u!` lines before the first document
+    preamble: String,
+    documents: Vec<DocumentText>,
+}
+
+/// One property change: `property_path` uses the same syntax as prefab modifications
+/// (`m_LocalPosition.x`, `m_Children.Array.data[0]`, `InitialModule.startLifetime.scalar`)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyEdit {
+    pub file_id: i64,
+    pub property_path: String,
+    pub value: Value,
+}
+
+// Một dòng YAML block: `<indent>[- ]key: rest`
+struct LineInfo<'a> {
+    indent: usize,
+    dash: bool,
+    /// Column of the content after `- ` (same as `indent` when there is no dash)
+    content_indent: usize,
+    key: Option<&'a str>,
+    /// Text after `key: ` (or the whole content when there is no key), without line ending
+    rest: &'a str,
+    /// Byte offset of `rest` in the line
+    rest_start: usize,
+}
+
+fn line_info(line: &str) -> LineInfo<'_> {
+    let body = line.trim_end_matches(['\n', '\r']);
+    let indent = body.len() - body.trim_start_matches(' ').len();
+    let mut content_start = indent;
+    let dash = body[indent..].starts_with("- ") || &body[indent..] == "-";
+    if dash {
+        content_start = (indent + 2).min(body.len());
+    }
+    let content = &body[content_start..];
+
+    let mut key = None;
+    let mut rest_start = content_start;
+    let starts_plain = !content.starts_with(['{', '[', '\'', '"']);
+    if starts_plain {
+        if let Some(pos) = content.find(": ") {
+            key = Some(&content[..pos]);
+            rest_start = content_start + pos + 2;
+        } else if let Some(stripped) = content.strip_suffix(':') {
+            key = Some(stripped);
+            rest_start = body.len();
+        }
+    }
+    LineInfo {
+        indent,
+        dash,
+        content_indent: content_start,
+        key,
+        rest: body[rest_start..].trim_end(),
+        rest_start,
+    }
+}
+
+// Dòng thuộc block của key ở cột `key_indent`: thụt sâu hơn, hoặc sequence `- ` cùng cột
+fn is_child(line: &LineInfo, key_indent: usize) -> bool {
+    line.indent > key_indent || (line.indent == key_indent && line.dash)
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+// "m_Children.Array.data[2]" -> [Key(m_Children), Index(2)]
+fn path_segments(path: &str) -> Vec<Segment<'_>> {
+    path.split('.')
+        .filter(|s| *s != "Array")
+        .map(|s| {
+            match s
+                .strip_prefix("data[")
+                .and_then(|s| s.strip_suffix(']'))
+                .and_then(|i| i.parse().ok())
+            {
+                Some(i) => Segment::Index(i),
+                None => Segment::Key(s),
+            }
+        })
+        .collect()
+}
+
+impl UnityYamlFile {
+    pub fn parse(content: &str) -> Self {
+        let mut preamble = String::new();
+        let mut documents: Vec<DocumentText> = Vec::new();
+        for line in content.split_inclusive('\n') {
+            if line.starts_with("--- ") {
+                let file_id = line
+                    .split_whitespace()
+                    .nth(2)
+                    .and_then(|id| id.trim_start_matches('&').parse().ok())
+                    .unwrap_or(0);
+                documents.push(DocumentText {
+                    header: line.to_string(),
+                    file_id,
+                    lines: Vec::new(),
+                });
+            } else if let Some(document) = documents.last_mut() {
+                document.lines.push(line.to_string());
+            } else {
+                preamble.push_str(line);
+            }
+        }
+        UnityYamlFile {
+            preamble,
+            documents,
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read Unity file: {}", e))?;
+        Ok(Self::parse(&content))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|e| format!("Failed to write Unity file: {}", e))
+    }
+
+    /// Replace the value at `property_path` inside document `&file_id`
+    pub fn set_property(
+        &mut self,
+        file_id: i64,
+        property_path: &str,
+        value: &Value,
+    ) -> Result<(), String> {
+        let document = self
+            .documents
+            .iter_mut()
+            .find(|d| d.file_id == file_id)
+            .ok_or_else(|| format!("Document not found: &{}", file_id))?;
+        let segments = path_segments(property_path);
+        if segments.is_empty() {
+            return Err("Property path is empty".to_string());
+        }
+        let not_found = || format!("Property not found in &{}: {}", file_id, property_path);
+
+        // Dòng đầu là `TypeName:`, các key của body nằm ở cột tiếp theo
+        let lines = &mut document.lines;
+        let mut start = 1;
+        let mut end = lines.len();
+        let mut indent = lines
+            .iter()
+            .skip(1)
+            .find(|l| !is_blank(l))
+            .map(|l| line_info(l).indent)
+            .ok_or_else(not_found)?;
+
+        for (i, segment) in segments.iter().enumerate() {
+            let remaining = &segments[i + 1..];
+            let line = match segment {
+                Segment::Key(key) => (start..end).find(|&j| {
+                    let info = line_info(&lines[j]);
+                    info.content_indent == indent && info.key == Some(*key)
+                }),
+                Segment::Index(n) => (start..end)
+                    .filter(|&j| {
+                        let info = line_info(&lines[j]);
+                        info.dash && info.indent == indent
+                    })
+                    .nth(*n),
+            }
+            .ok_or_else(not_found)?;
+
+            let info = line_info(&lines[line]);
+            let rest = info.rest.to_string();
+            let rest_start = info.rest_start;
+            let content_indent = info.content_indent;
+            let item_indent = info.indent;
+            let has_key = info.key.is_some();
+            let is_key = matches!(segment, Segment::Key(_));
+            // `key:` không có block con: giá trị rỗng, có thể ghi đè như scalar
+            let empty_scalar = is_key
+                && rest.is_empty()
+                && remaining.is_empty()
+                && (line + 1..end)
+                    .find(|&j| !is_blank(&lines[j]))
+                    .is_none_or(|j| !is_child(&line_info(&lines[j]), content_indent));
+            // Phần tử `- key: value` là một block mapping bắt đầu ngay trên dòng `- `
+            let inline = empty_scalar || (!rest.is_empty() && (info.key.is_none() || is_key));
+
+            // Giá trị nằm ngay trên dòng: scalar hoặc flow mapping `{...}`
+            if inline {
+                // Unity ngắt scalar/flow dài sang các dòng thụt sâu hơn: gộp lại, ghi trên một dòng
+                let base = if has_key { content_indent } else { item_indent };
+                let value_end = lines[line + 1..end]
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, l)| !is_blank(l))
+                    .take_while(|(_, l)| line_info(l).indent > base)
+                    .last()
+                    .map_or(line + 1, |(i, _)| line + 2 + i);
+                let rest = std::iter::once(rest.as_str())
+                    .chain(lines[line + 1..value_end].iter().map(|l| l.trim()))
+                    .filter(|l| !l.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let new_rest = if remaining.is_empty() {
+                    replace_value(&rest, value)
+                } else {
+                    let keys: Option<Vec<&str>> = remaining
+                        .iter()
+                        .map(|s| match s {
+                            Segment::Key(k) => Some(*k),
+                            Segment::Index(_) => None,
+                        })
+                        .collect();
+                    keys.and_then(|keys| replace_flow_value(&rest, &keys, value))
+                }
+                .ok_or_else(not_found)?;
+                let original = &lines[line];
+                let ending = &original[original.trim_end_matches(['\n', '\r']).len()..];
+                let prefix = if empty_scalar {
+                    format!("{} ", original[..rest_start].trim_end())
+                } else {
+                    original[..rest_start].to_string()
+                };
+                lines[line] = format!("{}{}{}", prefix, new_rest, ending);
+                lines.drain(line + 1..value_end);
+                return Ok(());
+            }
+            if remaining.is_empty() {
+                return Err(format!(
+                    "Cannot replace a block value in &{}: {}",
+                    file_id, property_path
+                ));
+            }
+
+            match segment {
+                Segment::Key(_) => {
+                    end = (line + 1..end)
+                        .find(|&j| {
+                            !is_blank(&lines[j]) && !is_child(&line_info(&lines[j]), content_indent)
+                        })
+                        .unwrap_or(end);
+                    start = line + 1;
+                    let first_child = (start..end)
+                        .find(|&j| !is_blank(&lines[j]))
+                        .ok_or_else(not_found)?;
+                    indent = line_info(&lines[first_child]).indent;
+                }
+                Segment::Index(_) => {
+                    end = (line + 1..end)
+                        .find(|&j| {
+                            !is_blank(&lines[j]) && line_info(&lines[j]).indent <= item_indent
+                        })
+                        .unwrap_or(end);
+                    start = line;
+                    indent = content_indent;
+                }
+            }
+        }
+        Err(not_found())
+    }
+
+    /// Apply several edits, then check the edited documents still parse
+    pub fn apply_edits(&mut self, edits: &[PropertyEdit]) -> Result<(), String> {
+        for edit in edits {
+            self.set_property(edit.file_id, &edit.property_path, &edit.value)?;
+        }
+        let edited: HashSet<i64> = edits.iter().map(|e| e.file_id).collect();
+        if let Some(error) = parse_unity_documents(&self.to_string())
+            .into_iter()
+            .filter(|d| edited.contains(&d.file_id))
+            .find_map(|d| d.error)
+        {
+            return Err(format!("Edited file is no longer valid: {}", error));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for UnityYamlFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.preamble)?;
+        for document in &self.documents {
+            f.write_str(&document.header)?;
+            for line in &document.lines {
+                f.write_str(line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Tách `{a: 1, b: {c: 2}}` thành các cặp (key, value) ở cấp ngoài cùng, giữ vị trí byte
+fn flow_entries(flow: &str) -> Option<Vec<(&str, usize, usize)>> {
+    let inner_start = flow.find('{')? + 1;
+    let inner_end = flow.rfind('}')?;
+    let mut entries = Vec::new();
+    let mut depth = 0;
+    let mut entry_start = inner_start;
+    for (i, c) in flow[inner_start..inner_end].char_indices() {
+        let i = i + inner_start;
+        match c {
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(flow_entry(flow, entry_start, i)?);
+                entry_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !flow[entry_start..inner_end].trim().is_empty() {
+        entries.push(flow_entry(flow, entry_start, inner_end)?);
+    }
+    Some(entries)
+}
+
+fn flow_entry(flow: &str, start: usize, end: usize) -> Option<(&str, usize, usize)> {
+    let text = &flow[start..end];
+    let colon = text.find(':')?;
+    let value = &text[colon + 1..];
+    let value_start = start + colon + 1 + (value.len() - value.trim_start().len());
+    let value_end = start + colon + 1 + value.trim_end().len();
+    Some((text[..colon].trim(), value_start, value_end))
+}
+
+// Thay giá trị trong flow mapping, giữ nguyên thứ tự key và khoảng trắng
+fn replace_flow_value(flow: &str, keys: &[&str], value: &Value) -> Option<String> {
+    let (key, remaining) = keys.split_first()?;
+    let (_, value_start, value_end) = flow_entries(flow)?.into_iter().find(|(k, _, _)| k == key)?;
+    let current = &flow[value_start..value_end];
+    let replacement = if remaining.is_empty() {
+        replace_value(current, value)?
+    } else {
+        replace_flow_value(current, remaining, value)?
+    };
+    Some(format!(
+        "{}{}{}",
+        &flow[..value_start],
+        replacement,
+        &flow[value_end..]
+    ))
+}
+
+// Object thay từng key của flow mapping hiện có; tham chiếu thì viết lại đủ fileID/guid/type
+fn replace_value(current: &str, value: &Value) -> Option<String> {
+    match value {
+        Value::Object(map) if is_reference(map) => Some(format_reference(map)),
+        Value::Object(map) if current.starts_with('{') => {
+            let mut flow = current.to_string();
+            for (key, v) in map {
+                flow = replace_flow_value(&flow, &[key.as_str()], v)?;
+            }
+            Some(flow)
+        }
+        Value::Object(_) | Value::Array(_) => None,
+        scalar => Some(format_scalar(scalar)),
+    }
+}
+
+fn is_reference(map: &serde_json::Map<String, Value>) -> bool {
+    map.contains_key("fileID")
+        && map
+            .keys()
+            .all(|k| matches!(k.as_str(), "fileID" | "guid" | "type"))
+}
+
+// `{fileID: 21300000, guid: ..., type: 3}` theo đúng thứ tự Unity ghi
+fn format_reference(map: &serde_json::Map<String, Value>) -> String {
+    let parts: Vec<String> = ["fileID", "guid", "type"]
+        .iter()
+        .filter_map(|k| Some(format!("{}: {}", k, format_scalar(map.get(*k)?))))
+        .collect();
+    format!("{{{}}}", parts.join(", "))
+}
+
+fn format_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Bool(b) => if *b { "1" } else { "0" }.to_string(),
+        Value::Number(n) => match n.as_f64() {
+            Some(f) if n.is_f64() && f.fract() == 0.0 && f.abs() < 1e15 => format!("{}", f as i64),
+            _ => n.to_string(),
+        },
+        Value::String(s) => format_string(s),
+        other => other.to_string(),
+    }
+}
+
+// Chuỗi plain nếu an toàn, ngược lại dùng single-quoted như Unity; xuống dòng hay ký tự
+// điều khiển thì double-quoted có escape (single-quoted gộp `\n` thành dấu cách khi đọc lại)
+fn format_string(s: &str) -> String {
+    if s.chars().any(char::is_control) {
+        let mut escaped = String::from("\"");
+        for c in s.chars() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '"' => escaped.push_str("\\\""),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if c.is_control() => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+                c => escaped.push(c),
+            }
+        }
+        escaped.push('"');
+        return escaped;
+    }
+    let needs_quotes = s.is_empty()
+        || s.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+            '@', '`', ' ',
+        ])
+        || s.ends_with(' ')
+        || s.contains(": ")
+        || s.contains(" #")
+        || s.parse::<f64>().is_ok()
+        || matches!(s, "true" | "false" | "null" | "~");
+    if needs_quotes {
+        format!("'{}'", s.replace('\'', "''"))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Apply property edits to a prefab/scene/asset file in place, leaving everything else untouched
+#[tauri::command]
+pub async fn edit_unity_asset(
+    asset_path: String,
+    edits: Vec<PropertyEdit>,
+) -> Result<usize, String> {
+    let path = Path::new(&asset_path);
+    let mut file = UnityYamlFile::read(path)?;
+    file.apply_edits(&edits)?;
+    file.write(path)?;
+    Ok(edits.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PREFAB: &str = "%YAML 1.1
+%TAG !u! tag:unity3d.com,2011:
+--- !u!1 &100
+GameObject:
+  m_ObjectHideFlags: 0
+  m_Component:
+  - component: {fileID: 101}
+  - component: {fileID: 102}
+  m_Name: Hero
+  m_IsActive: 1
+--- !u!4 &101
+Transform:
+  m_GameObject: {fileID: 100}
+  m_LocalPosition: {x: 0, y: 0, z: 0}
+  m_Children: 
+  - {fileID: 201}
+  - {fileID: 400}
+  m_Father: {fileID: 0}
+--- !u!212 &102
+SpriteRenderer:
+  m_GameObject: {fileID: 100}
+  m_Sprite: {fileID: 21300000, guid: 5eee0001, type: 3}
+  m_Materials:
+  - {fileID: 10754, guid: 0000000000000000f000000000000000, type: 0}
+
+  m_SortingOrder: 2
+--- !u!4 &400 stripped
+Transform:
+  m_CorrespondingSourceObject: {fileID: 400, guid: aaaa1111, type: 3}
+  m_PrefabInstance: {fileID: 500}
+--- !u!1001 &500
+PrefabInstance:
+  m_Modification:
+    m_Modifications:
+    - target: {fileID: 400, guid: aaaa1111, type: 3}
+      propertyPath: m_LocalPosition.x
+      value: 1
+      objectReference: {fileID: 0}
+    - target: {fileID: 401, guid: aaaa1111, type: 3}
+      propertyPath: m_Name
+      value: Body
+      objectReference: {fileID: 0}
+    m_RemovedComponents: []
+  m_SourcePrefab: {fileID: 100100000, guid: aaaa1111, type: 3}";
+
+    // Dòng khác nhau giữa hai text (cùng số dòng)
+    fn changed_lines(before: &str, after: &str) -> Vec<String> {
+        assert_eq!(before.lines().count(), after.lines().count());
+        before
+            .lines()
+            .zip(after.lines())
+            .filter(|(a, b)| a != b)
+            .map(|(_, b)| b.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn round_trip_is_byte_identical() {
+        assert_eq!(UnityYamlFile::parse(PREFAB).to_string(), PREFAB);
+        let trailing = format!("{}\n\n", PREFAB);
+        assert_eq!(UnityYamlFile::parse(&trailing).to_string(), trailing);
+        assert_eq!(UnityYamlFile::parse("").to_string(), "");
+    }
+
+    #[test]
+    fn round_trip_keeps_crlf() {
+        let crlf = PREFAB.replace('\n', "\r\n");
+        assert_eq!(UnityYamlFile::parse(&crlf).to_string(), crlf);
+
+        let mut file = UnityYamlFile::parse(&crlf);
+        file.set_property(100, "m_Name", &json!("Villain")).unwrap();
+        file.set_property(101, "m_LocalPosition.x", &json!(1.5))
+            .unwrap();
+        let out = file.to_string();
+        assert_eq!(out.matches("\r\n").count(), crlf.matches("\r\n").count());
+        assert!(!out.replace("\r\n", "").contains('\n'));
+        assert!(out.contains("  m_Name: Villain\r\n"));
+        assert!(out.contains("  m_LocalPosition: {x: 1.5, y: 0, z: 0}\r\n"));
+    }
+
+    #[test]
+    fn stripped_documents_keep_their_header() {
+        let mut file = UnityYamlFile::parse(PREFAB);
+        file.set_property(400, "m_PrefabInstance", &json!({"fileID": 501}))
+            .unwrap();
+        let out = file.to_string();
+        assert_eq!(
+            changed_lines(PREFAB, &out),
+            ["  m_PrefabInstance: {fileID: 501}"]
+        );
+        assert!(out.contains("--- !u!4 &400 stripped\nTransform:\n"));
+    }
+
+    #[test]
+    fn same_indent_sequences() {
+        let mut file = UnityYamlFile::parse(PREFAB);
+        file.set_property(101, "m_Children.Array.data[1]", &json!({"fileID": 999}))
+            .unwrap();
+        file.set_property(101, "m_Father", &json!({"fileID": 7}))
+            .unwrap();
+        file.set_property(
+            100,
+            "m_Component.Array.data[0].component.fileID",
+            &json!(103),
+        )
+        .unwrap();
+        // Sequence cùng cột với key cha không được nuốt key đứng sau nó
+        file.set_property(100, "m_Name", &json!("Hero 2")).unwrap();
+        file.set_property(102, "m_SortingOrder", &json!(5)).unwrap();
+        file.set_property(
+            500,
+            "m_Modification.m_Modifications.Array.data[1].value",
+            &json!("Arm"),
+        )
+        .unwrap();
+        assert!(file
+            .set_property(101, "m_Children.Array.data[2]", &json!({"fileID": 1}))
+            .is_err());
+        assert_eq!(
+            changed_lines(PREFAB, &file.to_string()),
+            [
+                "  - component: {fileID: 103}",
+                "  m_Name: Hero 2",
+                "  - {fileID: 999}",
+                "  m_Father: {fileID: 7}",
+                "  m_SortingOrder: 5",
+                "      value: Arm",
+            ]
+        );
+    }
+
+    #[test]
+    fn flow_reference_rewrites() {
+        let mut file = UnityYamlFile::parse(PREFAB);
+        // Tham chiếu được viết lại đủ fileID/guid/type theo thứ tự của Unity
+        file.set_property(
+            102,
+            "m_Sprite",
+            &json!({"type": 3, "guid": "5eee0002", "fileID": 21300002}),
+        )
+        .unwrap();
+        file.set_property(102, "m_Materials.Array.data[0].guid", &json!("abcd"))
+            .unwrap();
+        file.set_property(
+            500,
+            "m_Modification.m_Modifications.Array.data[0].target",
+            &json!({"fileID": 402, "guid": "aaaa1111", "type": 3}),
+        )
+        .unwrap();
+        file.set_property(
+            500,
+            "m_Modification.m_Modifications.Array.data[0].objectReference",
+            &json!({"fileID": 0}),
+        )
+        .unwrap();
+        assert!(file
+            .set_property(102, "m_Sprite.missing", &json!(1))
+            .is_err());
+        assert_eq!(
+            changed_lines(PREFAB, &file.to_string()),
+            [
+                "  m_Sprite: {fileID: 21300002, guid: 5eee0002, type: 3}",
+                "  - {fileID: 10754, guid: abcd, type: 0}",
+                "    - target: {fileID: 402, guid: aaaa1111, type: 3}",
+            ]
+        );
+    }
+
+    const WRAPPED: &str = "%YAML 1.1
+--- !u!114 &7
+MonoBehaviour:
+  m_Text: Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod
+    tempor incididunt ut labore et dolore
+
+    magna aliqua
+  m_FontData:
+    m_Font: {fileID: 10102, guid: 0000000000000000e000000000000000,
+      type: 0}
+    m_FontSize: 14
+  m_Lines:
+  - first line that is long enough
+    to wrap
+  - second
+";
+
+    fn text_of(content: &str) -> Value {
+        parse_unity_documents(content)[0].body["m_Text"].clone()
+    }
+
+    #[test]
+    fn wrapped_scalars_are_replaced_whole() {
+        let mut file = UnityYamlFile::parse(WRAPPED);
+        file.apply_edits(&[PropertyEdit {
+            file_id: 7,
+            property_path: "m_Text".to_string(),
+            value: json!("Short"),
+        }])
+        .unwrap();
+        file.set_property(7, "m_Lines.Array.data[0]", &json!("one"))
+            .unwrap();
+        file.set_property(7, "m_FontData.m_Font.guid", &json!("abcd"))
+            .unwrap();
+        let out = file.to_string();
+        assert!(out.contains(
+            "  m_Text: Short\n  m_FontData:\n    m_Font: {fileID: 10102, guid: abcd, type: 0}\n    m_FontSize: 14\n"
+        ));
+        assert!(out.contains("  m_Lines:\n  - one\n  - second\n"));
+        assert_eq!(text_of(&out), json!("Short"));
+    }
+
+    #[test]
+    fn multi_line_strings_are_double_quoted() {
+        let mut file = UnityYamlFile::parse(WRAPPED);
+        file.set_property(7, "m_Text", &json!("Line 1\nLine \"2\"\t\\"))
+            .unwrap();
+        let out = file.to_string();
+        assert!(out.contains("  m_Text: \"Line 1\\nLine \\\"2\\\"\\t\\\\\"\n"));
+        assert_eq!(text_of(&out), json!("Line 1\nLine \"2\"\t\\"));
+    }
+}
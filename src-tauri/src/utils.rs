@@ -1,6 +1,10 @@
 use std::path::Path;
 
+use crate::unity::guid_index::{project_index, GuidIndexState};
+use crate::unity::render::render_prefab_png;
+use crate::unity::texture::merge_alpha;
 use serde_json::Value;
+use tauri::State;
 use unity_yaml_rust::Yaml;
 
 pub fn check_os() -> String {
@@ -253,6 +257,7 @@ pub fn crop_image_from_bytes(
     y: u32,
     width: u32,
     height: u32,
+    alpha_image_bytes: Option<Vec<u8>>,
 ) -> Result<Vec<u8>, String> {
     use image::{ImageBuffer, ImageFormat, RgbaImage};
     use std::io::Cursor;
@@ -267,7 +272,14 @@ pub fn crop_image_from_bytes(
         .map_err(|e| format!("Failed to load image: {}", e))?;
 
     // Convert to RGBA with 8-bit per channel for maximum quality
-    let rgba_img = img.to_rgba8();
+    let mut rgba_img = img.to_rgba8();
+
+    // ETC1 split alpha: alpha nằm trong kênh đỏ của texture riêng
+    if let Some(alpha_bytes) = alpha_image_bytes {
+        let alpha_img = image::load_from_memory(&alpha_bytes)
+            .map_err(|e| format!("Failed to load alpha image: {}", e))?;
+        merge_alpha(&mut rgba_img, &alpha_img.to_rgba8());
+    }
 
     // Get image dimensions
     let (img_width, img_height) = rgba_img.dimensions();
@@ -410,10 +422,22 @@ pub fn parse_asset_file(asset_content: String) -> Result<AssetCropInfo, String>
     let mut y = 0u32;
     let mut width = 0u32;
     let mut height = 0u32;
+    let mut alpha_texture_guid = None;
 
     for (i, line) in lines.iter().enumerate() {
         let trimmed = line.trim();
 
+        // alphaTexture: {fileID: 2800000, guid: <guid>, type: 3}
+        if alpha_texture_guid.is_none() {
+            if let Some(reference) = trimmed.strip_prefix("alphaTexture:") {
+                alpha_texture_guid = reference
+                    .split("guid:")
+                    .nth(1)
+                    .and_then(|rest| rest.split(',').next())
+                    .map(|guid| guid.trim().to_string());
+            }
+        }
+
         // Look for rect data pattern
         if trimmed.contains("m_Rect:") || trimmed.contains("rect:") {
             println!("[Asset] Found rect pattern at line {}: {}", i, trimmed);
@@ -457,6 +481,7 @@ pub fn parse_asset_file(asset_content: String) -> Result<AssetCropInfo, String>
         y,
         width,
         height,
+        alpha_texture_guid,
     })
 }
 
@@ -466,36 +491,62 @@ fn parse_float_from_line(line: &str) -> Option<f32> {
 }
 
 #[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AssetCropInfo {
     pub x: u32,
     pub y: u32,
     pub width: u32,
     pub height: u32,
+    /// Split alpha texture (ETC1) to pass to `crop_image_from_bytes`
+    pub alpha_texture_guid: Option<String>,
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn export_hero_avatar(
-    base64_data: String,
+    base64_data: Option<String>,
     hero_name: String,
     output_dir: String,
+    prefab_path: Option<String>,
+    project_path: Option<String>,
+    scale: Option<f64>,
+    padding: Option<u32>,
+    guid_index: State<'_, GuidIndexState>,
 ) -> Result<String, String> {
     use base64::{engine::general_purpose, Engine as _};
     use std::fs;
 
     println!("[Export] Exporting hero avatar: {}", hero_name);
 
-    // Parse base64 data (remove data:image/png;base64, prefix if present)
-    let base64_clean = if base64_data.starts_with("data:image/png;base64,") {
-        &base64_data[22..]
-    } else {
-        &base64_data
+    let image_bytes = match (prefab_path, base64_data) {
+        // Render trực tiếp từ prefab thay vì dùng ảnh do frontend ghép
+        (Some(prefab_path), _) => {
+            let project_path = project_path.ok_or("Project path is required to render a prefab")?;
+            let index = project_index(&guid_index, &project_path).await?;
+            render_prefab_png(
+                prefab_path,
+                &index,
+                scale.filter(|s| *s > 0.0).unwrap_or(1.0),
+                padding.unwrap_or(0),
+            )
+            .await?
+        }
+        (None, Some(base64_data)) => {
+            // Parse base64 data (remove data:image/png;base64, prefix if present)
+            let base64_clean = if base64_data.starts_with("data:image/png;base64,") {
+                &base64_data[22..]
+            } else {
+                &base64_data
+            };
+
+            // Decode base64 to bytes
+            general_purpose::STANDARD
+                .decode(base64_clean)
+                .map_err(|e| format!("Failed to decode base64: {}", e))?
+        }
+        (None, None) => return Err("Either a prefab path or base64 data is required".to_string()),
     };
 
-    // Decode base64 to bytes
-    let image_bytes = general_purpose::STANDARD
-        .decode(base64_clean)
-        .map_err(|e| format!("Failed to decode base64: {}", e))?;
-
     // Create output directory if it doesn't exist
     fs::create_dir_all(&output_dir)
         .map_err(|e| format!("Failed to create output directory: {}", e))?;
@@ -770,9 +821,11 @@ pub fn yaml_to_json(yaml: &Yaml) -> Result<Value, String> {
             }
             Ok(Value::Object(json_obj))
         }
-        Yaml::Alias(_) => Err("YAML aliases not supported".to_string()),
+        // Unity không dùng anchor/alias, coi như giá trị rỗng
+        Yaml::Alias(_) => Ok(Value::Null),
         Yaml::Null => Ok(Value::Null),
         Yaml::BadValue => Err("Bad YAML value".to_string()),
-        Yaml::Original(_) => Err("Original YAML value not supported".to_string()),
+        // Dòng gốc (header document, directive) được giữ nguyên dạng text
+        Yaml::Original(s) => Ok(Value::from(s.clone())),
     }
 }
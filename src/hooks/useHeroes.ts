@@ -1,6 +1,7 @@
 'use client';
 
 import type { Hero, HeroSkin } from '@/store/project/store';
+import type { GuidEntry } from '@/types/unity/guidIndex';
 import { useProjectStore } from '@/store/project/store';
 import { log } from '@/utils/logger';
 import { invoke } from '@tauri-apps/api/core';
@@ -17,6 +18,8 @@ export interface AssetCropInfo {
 	y: number;
 	width: number;
 	height: number;
+	/** Split alpha texture (ETC1 builds), merged into the crop */
+	alphaTextureGuid: string | null;
 }
 
 export function useHeroes() {
@@ -338,12 +341,25 @@ export function useHeroes() {
 
 				// Crop the avatar from the combined texture using asset information
 				try {
+					let alphaImageBytes: number[] | null = null;
+					if (cropInfo.alphaTextureGuid) {
+						const alphaEntry = await invoke<GuidEntry | null>('resolve_guid', {
+							projectPath,
+							guid: cropInfo.alphaTextureGuid,
+						});
+						if (alphaEntry) {
+							alphaImageBytes = await invoke<number[]>('read_file_as_bytes', {
+								path: `${projectPath}/${alphaEntry.path}`,
+							});
+						}
+					}
 					finalImageBytes = await invoke<number[]>('crop_image_from_bytes', {
 						imageBytes,
 						x: cropInfo.x,
 						y: cropInfo.y,
 						width: cropInfo.width,
 						height: cropInfo.height,
+						alphaImageBytes,
 					});
 					log.hero.asset(
 						hero.id,
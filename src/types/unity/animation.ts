@@ -0,0 +1,93 @@
+export type TrackKind = 'Float' | 'Vector3' | 'Quaternion';
+
+export interface TrackKeyframe {
+	time: number;
+	/** One value per component (1 for Float, 3 for Vector3, 4 for Quaternion) */
+	value: number[];
+	/** `null` means an infinite (stepped) tangent */
+	inSlope: (number | null)[];
+	outSlope: (number | null)[];
+	inWeight: number[];
+	outWeight: number[];
+	tangentMode: number;
+	weightedMode: number;
+}
+
+export interface AnimationTrack {
+	path: string;
+	attribute: string;
+	classId: number;
+	kind: TrackKind;
+	preInfinity: number;
+	postInfinity: number;
+	keyframes: TrackKeyframe[];
+}
+
+export interface SpriteKeyframe {
+	time: number;
+	guid: string | null;
+	fileId: number;
+}
+
+export interface SpriteTrack {
+	path: string;
+	attribute: string;
+	classId: number;
+	keyframes: SpriteKeyframe[];
+}
+
+export interface ClipSprite {
+	guid: string;
+	fileId: number;
+	path: string | null;
+	spriteBase64: string | null;
+	spriteRect: { x: number; y: number; width: number; height: number } | null;
+	offset: { x: number; y: number } | null;
+}
+
+export interface AnimationEventInfo {
+	time: number;
+	functionName: string;
+	stringParameter: string;
+	floatParameter: number;
+	intParameter: number;
+	objectReferenceGuid: string | null;
+}
+
+export interface AnimationClipData {
+	name: string;
+	path: string;
+	sampleRate: number;
+	startTime: number;
+	stopTime: number;
+	length: number;
+	loopTime: boolean;
+	wrapMode: number;
+	tracks: AnimationTrack[];
+	spriteTracks: SpriteTrack[];
+	sprites: ClipSprite[];
+	events: AnimationEventInfo[];
+}
+
+export interface TransformPose {
+	path: string;
+	localPosition: [number, number, number] | null;
+	localEulerAngles: [number, number, number] | null;
+	localRotation: [number, number, number, number] | null;
+	localScale: [number, number, number] | null;
+	/** Other animated float properties (m_IsActive, m_Color.a, m_FlipX...) */
+	floats: Record<string, number>;
+	sprite: SpriteKeyframe | null;
+}
+
+export interface PoseFrame {
+	time: number;
+	transforms: TransformPose[];
+}
+
+export interface ClipSamples {
+	clipName: string;
+	sampleRate: number;
+	length: number;
+	frames: PoseFrame[];
+}
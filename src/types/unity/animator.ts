@@ -0,0 +1,85 @@
+export interface AnimatorControllerGraph {
+	name: string;
+	path: string;
+	/** Original controller when the file is an AnimatorOverrideController */
+	baseControllerPath: string | null;
+	parameters: AnimatorParameterInfo[];
+	layers: AnimatorLayerInfo[];
+	clips: AnimationClipRef[];
+}
+
+export interface AnimatorParameterInfo {
+	name: string;
+	parameterType: 'Float' | 'Int' | 'Bool' | 'Trigger' | 'Unknown';
+	defaultValue: number | boolean | null;
+}
+
+export interface AnimatorLayerInfo {
+	name: string;
+	blendingMode: number;
+	defaultWeight: number;
+	syncedLayerIndex: number;
+	stateMachine: AnimatorStateMachineInfo | null;
+}
+
+export interface AnimatorStateMachineInfo {
+	fileId: number;
+	name: string;
+	defaultStateId: number | null;
+	states: AnimatorStateInfo[];
+	anyStateTransitions: AnimatorTransitionInfo[];
+	entryTransitions: AnimatorTransitionInfo[];
+	stateMachines: AnimatorStateMachineInfo[];
+}
+
+export interface AnimatorStateInfo {
+	fileId: number;
+	name: string;
+	tag: string;
+	speed: number;
+	cycleOffset: number;
+	writeDefaultValues: boolean;
+	isDefault: boolean;
+	motion: AnimatorMotionInfo | null;
+	transitions: AnimatorTransitionInfo[];
+}
+
+export interface AnimatorMotionInfo {
+	name: string;
+	fileId: number;
+	guid: string | null;
+	clipPath: string | null;
+	isBlendTree: boolean;
+	blendParameter: string | null;
+	threshold: number | null;
+	children: AnimatorMotionInfo[];
+}
+
+export interface AnimatorTransitionInfo {
+	fileId: number;
+	name: string;
+	destinationStateId: number | null;
+	destinationName: string | null;
+	isExit: boolean;
+	muted: boolean;
+	solo: boolean;
+	hasExitTime: boolean;
+	exitTime: number;
+	duration: number;
+	offset: number;
+	hasFixedDuration: boolean;
+	conditions: AnimatorConditionInfo[];
+}
+
+export interface AnimatorConditionInfo {
+	mode: 'If' | 'IfNot' | 'Greater' | 'Less' | 'Equals' | 'NotEqual' | 'Unknown';
+	parameter: string;
+	threshold: number;
+}
+
+export interface AnimationClipRef {
+	name: string;
+	guid: string | null;
+	fileId: number;
+	path: string | null;
+}
@@ -0,0 +1,22 @@
+import type { ScriptInfo } from './monoScript';
+
+/** Rows of one ScriptableObject class, or of an array field of that class */
+export interface DataTable {
+	/** `Namespace.ClassName`, or `Namespace.ClassName.field` for a child table */
+	name: string;
+	scriptGuid: string;
+	script: ScriptInfo | null;
+	/** Table whose rows own this one (`_parentId` column) */
+	parent: string | null;
+	columns: string[];
+	rows: Record<string, unknown>[];
+}
+
+export interface DataTableSummary {
+	name: string;
+	parent: string | null;
+	rowCount: number;
+	columnCount: number;
+	jsonPath: string | null;
+	csvPath: string | null;
+}
@@ -0,0 +1,7 @@
+export interface DependencyEntry {
+	guid: string;
+	/** Project-relative path, `null` for built-in or missing assets */
+	path: string | null;
+	/** Hops from the asset that was asked about (1 = direct reference) */
+	depth: number;
+}
@@ -0,0 +1,11 @@
+/** A raw `--- !u!<classId> &<fileId>` document of a Unity YAML file */
+export interface UnityDocument {
+	classId: number;
+	fileId: number;
+	/** Placeholder for an object owned by a PrefabInstance */
+	stripped: boolean;
+	/** Unity type name (`Transform`, `BoxCollider2D`...) */
+	typeName: string;
+	/** Properties without the outer `TypeName:` key */
+	body: unknown;
+}
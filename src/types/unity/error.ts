@@ -0,0 +1,22 @@
+export type UnityParseError =
+	| { kind: 'badHeader'; line: number; header: string }
+	| { kind: 'yaml'; fileId: number; message: string }
+	| { kind: 'unknownClassId'; classId: number; fileId: number; typeName: string }
+	| { kind: 'schemaMismatch'; fileId: number; path: string; message: string }
+	| { kind: 'missingGuid'; guid: string }
+	| {
+			kind: 'missingTexture';
+			spriteGuid: string;
+			textureGuid: string | null;
+			message: string;
+	  }
+	| { kind: 'unreadablePrefab'; guid: string; path: string; message: string }
+	| { kind: 'recursivePrefab'; guid: string; depth: number };
+
+export interface DocumentDiagnostic {
+	fileId: number;
+	classId: number;
+	/** GameObject owning the document, when the document says so */
+	gameObjectId: number | null;
+	error: UnityParseError;
+}
@@ -0,0 +1,6 @@
+export interface GuidEntry {
+	/** Asset path relative to the project root */
+	path: string;
+	importer: string;
+	fileIds: number[];
+}
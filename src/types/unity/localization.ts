@@ -0,0 +1,10 @@
+/** Localized texts of one key: language code -> text */
+export type LocalizedTexts = Record<string, string>;
+
+/** Every localized string found in the project: key -> language -> text */
+export interface LocalizationTable {
+	languages: string[];
+	entries: Record<string, LocalizedTexts>;
+	/** Project-relative paths the entries were read from */
+	sources: string[];
+}
@@ -0,0 +1,8 @@
+export interface ScriptInfo {
+	guid: string;
+	className: string | null;
+	namespace: string | null;
+	assembly: string | null;
+	/** Project-relative path of the `.cs`, MonoScript `.asset` or `.dll` */
+	path: string | null;
+}
@@ -0,0 +1,10 @@
+export interface PackageSummary {
+	outputPath: string;
+	/** Assets copied, the exported asset included */
+	assetCount: number;
+	/** Files written, `.meta` files included */
+	fileCount: number;
+	totalSize: number;
+	/** Referenced GUIDs that are not in the project (built-in or missing assets) */
+	missing: string[];
+}
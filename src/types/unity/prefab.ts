@@ -1,3 +1,8 @@
+import type { UnityDocument } from './document';
+import type { DocumentDiagnostic, UnityParseError } from './error';
+import type { ScriptInfo } from './monoScript';
+import type { SerializedField } from './reference';
+
 export interface HierarchyNode {
 	name: string;
 	type: Type;
@@ -8,7 +13,30 @@ export interface HierarchyNode {
 	particle_system: ParticleSystem | null;
 	animation_clips: string[] | null;
 	mono_behaviours: MonoBehaviour[];
+	/** Components without a typed view, as raw property trees */
+	raw_components: UnityDocument[];
 	children: HierarchyNodeChild[];
+	/** Local-to-world matrix, column major like Unity's Matrix4x4 */
+	world_matrix: number[];
+	/** World-space bounds of this node's sprite */
+	sprite_bounds: WorldBounds | null;
+	/** Bounds of the active sprites in this subtree (on the root: the whole prefab) */
+	bounds: WorldBounds | null;
+	/** Why a component of this node could not be read */
+	diagnostics?: UnityParseError[];
+}
+
+/** Result of `prefab_hierarchy`: the root node plus per-document diagnostics */
+export interface PrefabHierarchy extends HierarchyNode {
+	documentDiagnostics: DocumentDiagnostic[];
+}
+
+/** World-space axis-aligned rectangle on the XY plane, in Unity units */
+export interface WorldBounds {
+	minX: number;
+	minY: number;
+	maxX: number;
+	maxY: number;
 }
 
 export interface Animator {
@@ -24,7 +52,16 @@ export interface HierarchyNodeChild {
 	sprite_renderer: SpriteRenderer | null;
 	animator: null;
 	mono_behaviours: object[];
+	/** Components without a typed view, as raw property trees */
+	raw_components: UnityDocument[];
 	children: ChildChild[];
+	/** Local-to-world matrix, column major like Unity's Matrix4x4 */
+	world_matrix: number[];
+	/** World-space bounds of this node's sprite */
+	sprite_bounds: WorldBounds | null;
+	/** Bounds of the active sprites in this subtree (on the root: the whole prefab) */
+	bounds: WorldBounds | null;
+	diagnostics?: UnityParseError[];
 }
 
 export interface ChildChild {
@@ -35,13 +72,24 @@ export interface ChildChild {
 	sprite_renderer: SpriteRenderer | null;
 	animator: null;
 	mono_behaviours: object[];
+	/** Components without a typed view, as raw property trees */
+	raw_components: UnityDocument[];
 	children: ChildChild[];
+	/** Local-to-world matrix, column major like Unity's Matrix4x4 */
+	world_matrix: number[];
+	/** World-space bounds of this node's sprite */
+	sprite_bounds: WorldBounds | null;
+	/** Bounds of the active sprites in this subtree (on the root: the whole prefab) */
+	bounds: WorldBounds | null;
+	diagnostics?: UnityParseError[];
 }
 
 export interface SpriteRenderer {
 	color: Color;
 	size: Size;
 	spriteGuid: string;
+	/** fileID of the sprite inside spriteGuid (sprite sheet entry) */
+	spriteFileId: number;
 	spriteBase64: string;
 	spriteRect: {
 		x: number;
@@ -49,6 +97,15 @@ export interface SpriteRenderer {
 		width: number;
 		height: number;
 	};
+	/** Normalized pivot inside spriteRect */
+	pivot: { x: number; y: number } | null;
+	pixelsPerUnit: number | null;
+	/** Left (x), bottom (y), right (z), top (w) in pixels, for 9-slicing */
+	border: { x: number; y: number; z: number; w: number } | null;
+	/** 0 = simple, 1 = sliced, 2 = tiled (drawn at `size`) */
+	drawMode: number;
+	/** 0 = continuous, 1 = adaptive */
+	tileMode: number;
 	sortingOrder: number;
 	flipX: number;
 	flipY: number;
@@ -92,6 +149,10 @@ export interface MonoBehaviour {
 	isUi: number | null;
 	pauseUpdateUi: number | null;
 	scriptGuid: string;
+	script: ScriptInfo | null;
+	enabled: boolean;
+	/** Every serialized field of the script; references carry a resolved `target` */
+	fields: Record<string, SerializedField>;
 }
 
 // Animation interfaces
@@ -269,3 +330,11 @@ export interface ParticleBurst {
 	repeatInterval: number;
 	probability: number;
 }
+
+export interface SceneHierarchy {
+	name: string;
+	path: string;
+	/** Root GameObjects in hierarchy window order */
+	roots: HierarchyNode[];
+	documentDiagnostics: DocumentDiagnostic[];
+}
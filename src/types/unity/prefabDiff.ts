@@ -0,0 +1,48 @@
+/** One value that differs, `path` is relative to the component (`localPosition.x`) */
+export interface FieldChange {
+	path: string;
+	old: unknown;
+	new: unknown;
+}
+
+export interface SpriteChange {
+	/** `null` on the side that has no SpriteRenderer */
+	oldGuid: string | null;
+	newGuid: string | null;
+	/** fileID inside the texture (which sprite of a sheet) */
+	oldFileId: number | null;
+	newFileId: number | null;
+	/** Sprite name in each version (sheet entry name, or the asset name) */
+	oldName: string | null;
+	newName: string | null;
+}
+
+export type ChangeKind = 'added' | 'removed' | 'changed';
+
+export interface MonoBehaviourDiff {
+	scriptGuid: string;
+	/** `Namespace.ClassName` when the script resolves */
+	className: string | null;
+	kind: ChangeKind;
+	fields: FieldChange[];
+}
+
+/** Changes of one GameObject that exists in both versions */
+export interface NodeDiff {
+	path: string;
+	isActive: FieldChange | null;
+	transform: FieldChange[];
+	sprite: SpriteChange | null;
+	spriteRenderer: FieldChange[];
+	animator: FieldChange[];
+	particleSystem: FieldChange[];
+	monoBehaviours: MonoBehaviourDiff[];
+}
+
+export interface PrefabDiff {
+	/** GameObject paths (`Hero/Body/Arm`) only in the new version */
+	added: string[];
+	/** GameObject paths only in the old version */
+	removed: string[];
+	changed: NodeDiff[];
+}
@@ -0,0 +1,29 @@
+/** Where a `{fileID, guid}` reference points to */
+export interface ObjectReference {
+	fileId: number;
+	guid: string | null;
+	/** Unity type of the local object (`GameObject`, `Transform`...), unknown for assets */
+	typeName: string | null;
+	/** GameObject name, or the asset file name for references into another file */
+	name: string | null;
+	/** Project-relative asset path for references into another file */
+	path: string | null;
+	missing: boolean;
+}
+
+/** `{fileID, guid, type}` as serialized by Unity, with the resolved target */
+export interface SerializedReference {
+	fileID: number;
+	guid?: string;
+	type?: number;
+	target?: ObjectReference;
+}
+
+export type SerializedField =
+	| null
+	| boolean
+	| number
+	| string
+	| SerializedReference
+	| SerializedField[]
+	| { [key: string]: SerializedField };
@@ -0,0 +1,40 @@
+export type RenderFormat = 'pngSequence' | 'gif' | 'apng';
+
+export interface AnimationRenderOptions {
+	/** Clip chosen by path, GUID, or by name inside the prefab's AnimatorController */
+	clipPath?: string | null;
+	clipGuid?: string | null;
+	clipName?: string | null;
+	/** File for GIF/APNG, directory for a PNG sequence */
+	outputPath: string;
+	format: RenderFormat;
+	/** Defaults to the clip's sample rate */
+	fps?: number | null;
+	/** Multiplier applied to the sprites' pixels-per-unit */
+	scale?: number | null;
+	padding?: number | null;
+}
+
+export interface RenderedAnimation {
+	clipName: string;
+	frameCount: number;
+	fps: number;
+	width: number;
+	height: number;
+	outputPaths: string[];
+}
+
+export interface ImageRenderOptions {
+	/** PNG file to write; the image is returned as base64 when omitted */
+	outputPath?: string | null;
+	/** Multiplier applied to the sprites' pixels-per-unit */
+	scale?: number | null;
+	padding?: number | null;
+}
+
+export interface RenderedImage {
+	width: number;
+	height: number;
+	outputPath: string | null;
+	base64: string | null;
+}
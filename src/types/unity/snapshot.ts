@@ -0,0 +1,51 @@
+/** One asset of a snapshot, like `AssetInfo` in ProjectAnalyzer.cs */
+export interface AssetSnapshot {
+	path: string;
+	guid: string;
+	type: string;
+	fileSize: number;
+	/** SHA-256 of the file content, `null` for folders */
+	hash: string | null;
+}
+
+export interface SnapshotInfo {
+	outputPath: string;
+	version: string;
+	assetCount: number;
+	totalSize: number;
+}
+
+/** Same GUID, new path */
+export interface MovedAsset {
+	guid: string;
+	from: string;
+	to: string;
+	/** Content changed as well */
+	modified: boolean;
+}
+
+/** Same GUID and path, different content */
+export interface ModifiedAsset {
+	guid: string;
+	path: string;
+	oldSize: number;
+	newSize: number;
+}
+
+export interface TypeChanges {
+	added: AssetSnapshot[];
+	removed: AssetSnapshot[];
+	moved: MovedAsset[];
+	modified: ModifiedAsset[];
+}
+
+export interface SnapshotComparison {
+	previousVersion: string;
+	currentVersion: string;
+	added: number;
+	removed: number;
+	moved: number;
+	modified: number;
+	/** Changes grouped by asset type (`Texture2D`, `GameObject`...) */
+	byType: Record<string, TypeChanges>;
+}
@@ -0,0 +1,9 @@
+/** Argument of `edit_unity_asset` */
+export interface PropertyEdit {
+	/** `&fileID` of the document to edit */
+	fileId: number;
+	/** Same syntax as prefab modifications: `m_LocalPosition.x`, `m_Children.Array.data[0]` */
+	propertyPath: string;
+	/** Scalar, `{fileID, guid, type}` reference, or an object of flow-mapping fields */
+	value: unknown;
+}